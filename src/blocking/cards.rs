@@ -0,0 +1,213 @@
+//! Get game cards (e.g.: "Kokusho, the Evening Star", "Island", "Black Lotus").
+//!
+//! Blocking version of `crate::cards`. The filters are the same, see `crate::cards::filter()`.
+use crate::cards::{self, Card, CardKey};
+use crate::catalog::{Catalog, UnknownValue, Validation};
+use crate::client::Client;
+use crate::stats::StatValue;
+use futures::executor::{block_on, block_on_stream};
+use http::StatusCode;
+
+/// Function to get all cards.
+///
+/// The call will return a maximum of 100 cards. To get more, it is necessary to use the `page` filter.
+///
+/// # Example
+/// ```rust,no_run
+/// use mtgsdk::blocking::cards;
+/// let cards = cards::all();
+/// assert_eq!(cards.unwrap().get(0).unwrap().name.chars().collect::<Vec<char>>()[0], 'A');
+///```
+///
+/// # Errors
+/// If the call fails, it will return a `Err(StatusCode)`.
+/// To see the possible return values, check the [API docs](https://docs.magicthegathering.io/#documentationerrors).
+pub fn all() -> Result<Vec<Card>, StatusCode> {
+    all_with(&super::client())
+}
+
+/// Function to get all cards with the given `Client` (see `crate::client`).
+///
+/// # Errors
+/// If the call fails, it will return a `Err(StatusCode)`.
+/// To see the possible return values, check the [API docs](https://docs.magicthegathering.io/#documentationerrors).
+pub fn all_with(client: &Client) -> Result<Vec<Card>, StatusCode> {
+    block_on(cards::all_with(client))
+}
+
+/// Function to get a single card, by its `multiverseid` or its `id` (see `crate::cards::find()`).
+///
+/// # Example
+/// ```rust,no_run
+/// use mtgsdk::blocking::cards;
+/// let cards = cards::find(386616);
+/// assert_eq!(cards.unwrap().name, "Narset, Enlightened Master");
+///```
+///
+/// # Errors
+/// If the call fails, it will return a `Err(StatusCode)`.
+/// To see the possible return values, check the [API docs](https://docs.magicthegathering.io/#documentationerrors).
+pub fn find<K: Into<CardKey>>(id: K) -> Result<Card, StatusCode> {
+    find_with(&super::client(), id)
+}
+
+/// Function to get a single card with the given `Client` (see `crate::client`).
+///
+/// # Errors
+/// If the call fails, it will return a `Err(StatusCode)`.
+/// To see the possible return values, check the [API docs](https://docs.magicthegathering.io/#documentationerrors).
+pub fn find_with<K: Into<CardKey>>(client: &Client, id: K) -> Result<Card, StatusCode> {
+    block_on(cards::find_with(client, id))
+}
+
+/// Function to get a card by its exact name, optionally in a given set (see `crate::cards::find_by_name()`).
+///
+/// # Example
+/// ```rust,no_run
+/// use mtgsdk::blocking::cards;
+/// let card = cards::find_by_name("Fire", Some("APC"));
+/// assert_eq!(card.unwrap().set_field, "APC");
+///```
+///
+/// # Errors
+/// If there is no such card (in the set, if any), it will return a `Err(StatusCode::NOT_FOUND)`.
+/// If the call fails, it will return a `Err(StatusCode)`.
+/// To see the possible return values, check the [API docs](https://docs.magicthegathering.io/#documentationerrors).
+pub fn find_by_name(name: &str, set: Option<&str>) -> Result<Card, StatusCode> {
+    find_by_name_with(&super::client(), name, set)
+}
+
+/// Function to get a card by its exact name with the given `Client` (see `find_by_name()` and `crate::client`).
+///
+/// # Errors
+/// If there is no such card (in the set, if any), it will return a `Err(StatusCode::NOT_FOUND)`.
+/// If the call fails, it will return a `Err(StatusCode)`.
+/// To see the possible return values, check the [API docs](https://docs.magicthegathering.io/#documentationerrors).
+pub fn find_by_name_with(
+    client: &Client,
+    name: &str,
+    set: Option<&str>,
+) -> Result<Card, StatusCode> {
+    for card in Where(cards::by_name(name, set).client(client)).iter() {
+        let card = card?;
+        if cards::is_printing(&card, name, set) {
+            return Ok(card);
+        }
+    }
+    Err(StatusCode::NOT_FOUND)
+}
+
+#[doc(hidden)]
+#[derive(Clone, Debug)]
+pub struct Where<'a>(cards::Where<'a>);
+
+/// Function to get all card matching the query filters.
+///
+/// To use it, call `filter()` followed by the desired filters and then close with `all()`.
+///
+/// # Example
+/// ```rust,no_run
+/// use mtgsdk::blocking::cards;
+/// let cards = cards::filter()
+///     .supertypes("legendary")
+///     .types("creature")
+///     .colors("red,white")
+///     .all();
+/// assert!(cards.unwrap().iter().any(|card| card.name == "Breya, Etherium Shaper"));
+///```
+///
+/// # Errors
+/// If the call fails, it will return a `Err(StatusCode)`.
+/// To see the possible return values, check the [API docs](https://docs.magicthegathering.io/#documentationerrors).
+pub fn filter<'a>() -> Where<'a> {
+    Where(cards::filter().client(&super::client()))
+}
+
+// The filters are the ones of `cards::Where`
+macro_rules! filters {
+    ($($name:ident: $input:ty),* $(,)?) => {
+        $(
+            pub fn $name(self, input: $input) -> Self {
+                Where(self.0.$name(input))
+            }
+        )*
+    };
+}
+
+impl<'a> Where<'a> {
+    /// Makes the call with the given `Client` instead of the default one (see `crate::client`).
+    pub fn client(self, client: &Client) -> Self {
+        Where(self.0.client(client))
+    }
+
+    filters! {
+        name: &'a str,
+        layout: &'a str,
+        cmc: u64,
+        colors: &'a str,
+        color_identity: &'a str,
+        type_field: &'a str,
+        supertypes: &'a str,
+        types: &'a str,
+        subtypes: &'a str,
+        rarity: &'a str,
+        set_field: &'a str,
+        set_name: &'a str,
+        text: &'a str,
+        flavor: &'a str,
+        artist: &'a str,
+        number: &'a str,
+        power: &'a str,
+        toughness: &'a str,
+        power_value: StatValue,
+        power_at_least: i64,
+        power_at_most: i64,
+        toughness_value: StatValue,
+        toughness_at_least: i64,
+        toughness_at_most: i64,
+        loyalty: &'a str,
+        language: &'a str,
+        game_format: &'a str,
+        legality: &'a str,
+        page: u64,
+        page_size: u64,
+        order_by: &'a str,
+        random: &'a str,
+        contains: &'a str,
+        id: &'a str,
+        multiverseid: u64,
+    }
+
+    /// Checks the `types()`, `subtypes()`, `supertypes()` and `game_format()` values against the catalog.
+    ///
+    /// Blocking version of `crate::cards::Where::validate()`.
+    ///
+    /// # Errors
+    /// With `Validation::Reject`, if any value is unknown, it will return a `Err(Vec<UnknownValue>)`.
+    pub fn validate(
+        self,
+        catalog: &Catalog,
+        validation: Validation,
+    ) -> Result<(Self, Vec<UnknownValue>), Vec<UnknownValue>> {
+        let (filter, unknown) = self.0.validate(catalog, validation)?;
+        Ok((Where(filter), unknown))
+    }
+
+    pub fn all(self) -> Result<Vec<Card>, StatusCode> {
+        block_on(self.0.all())
+    }
+
+    /// Returns every card matching the filters, one at a time, fetching the pages as needed.
+    ///
+    /// Blocking version of `crate::cards::Where::stream()`.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use mtgsdk::blocking::cards;
+    /// let cards: Result<Vec<_>, _> = cards::filter().set_field("KTK").iter().collect();
+    /// assert!(cards.unwrap().len() > 100);
+    ///```
+    pub fn iter(self) -> impl Iterator<Item = Result<Card, StatusCode>> + 'a {
+        block_on_stream(Box::pin(self.0.stream()))
+    }
+}
@@ -0,0 +1,33 @@
+//! Get game formats (e.g.: Standard, Modern, Onslaught Block).
+//!
+//! Blocking version of `crate::formats`.
+use crate::client::Client;
+use crate::formats;
+use futures::executor::block_on;
+use http::StatusCode;
+use std::collections::HashSet;
+
+/// Function to get all formats.
+///
+/// # Example
+/// ```rust,no_run
+/// use mtgsdk::blocking::formats;
+/// let formats = formats::all();
+/// assert!(formats.unwrap().contains("Modern"));
+///```
+///
+/// # Errors
+/// If the call fails, it will return a `Err(StatusCode)`.
+/// To see the possible return values, check the [API docs](https://docs.magicthegathering.io/#documentationerrors).
+pub fn all() -> Result<HashSet<String>, StatusCode> {
+    all_with(&super::client())
+}
+
+/// Function to get all formats with the given `Client` (see `crate::client`).
+///
+/// # Errors
+/// If the call fails, it will return a `Err(StatusCode)`.
+/// To see the possible return values, check the [API docs](https://docs.magicthegathering.io/#documentationerrors).
+pub fn all_with(client: &Client) -> Result<HashSet<String>, StatusCode> {
+    block_on(formats::all_with(client))
+}
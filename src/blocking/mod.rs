@@ -0,0 +1,110 @@
+//! Blocking (synchronous) version of the API calls.
+//!
+//! Requires the `blocking` feature. No async runtime is needed: each module mirrors its async counterpart
+//! (e.g.: `blocking::cards` mirrors `cards`), with the same functions and filters, minus the `.await`.
+//!
+//! These calls must not be made from inside an async runtime (e.g.: within `#[tokio::main]`), use the async API there.
+//!
+//! The calls go through the same `client::Client` as the async ones, run to completion. By default, its transport is
+//! `client::BlockingTransport`; the calls ending in `_with` and the filters' `client()` take another client
+//! (whose transport must not need an async runtime either).
+//!
+//! # Example
+//! ```rust,no_run
+//! use mtgsdk::blocking::cards;
+//!
+//! let cards = cards::filter()
+//!     .name("Karn")
+//!     .all();
+//! assert!(cards.unwrap().iter().any(|card| card.name == "Karn Liberated"));
+//! ```
+pub mod cards;
+pub mod formats;
+pub mod sets;
+pub mod subtypes;
+pub mod supertypes;
+pub mod types;
+
+use crate::client::{BlockingTransport, Client};
+
+// Client of the calls made without one
+fn client() -> Client {
+    Client::with_transport(BlockingTransport::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::blocking::*;
+    use crate::cards::Card;
+    use crate::catalog::{Catalog, Validation};
+    use crate::client::tests::Fake;
+    use crate::client::Client;
+    use crate::fixtures::{page, strings};
+    use crate::sets::Set;
+
+    #[test]
+    fn blocking_find_card() {
+        let card = serde_json::json!({ "card": Card {
+            name: "Narset, Enlightened Master".to_string(),
+            ..Default::default()
+        }});
+        let client = Client::with_transport(Fake::new(&[("/v1/cards/386616", &card.to_string())]));
+        let cards = cards::find_with(&client, 386616);
+        assert_eq!(cards.unwrap().name, "Narset, Enlightened Master");
+    }
+
+    #[test]
+    fn blocking_filter_set() {
+        let mirage = Set {
+            name: "Mirage".to_string(),
+            ..Default::default()
+        };
+        let sets = serde_json::json!({ "sets": [mirage] }).to_string();
+        let client = Client::with_transport(Fake::new(&[("/v1/sets/?name=mira", &sets)]));
+        let sets = sets::filter().client(&client).name("mira").all();
+        assert_eq!(sets.unwrap().pop().unwrap().name, "Mirage");
+    }
+
+    #[test]
+    fn blocking_card_pages() {
+        let client = Client::with_transport(Fake::new(&[
+            ("/v1/cards/?set=KTK&pageSize=2&page=1", &page(&["a", "b"])),
+            ("/v1/cards/?set=KTK&pageSize=2&page=2", &page(&["c"])),
+        ]));
+        let cards: Result<Vec<_>, _> = cards::filter()
+            .client(&client)
+            .set_field("KTK")
+            .page_size(2)
+            .iter()
+            .collect();
+        let names: Vec<String> = cards.unwrap().into_iter().map(|card| card.name).collect();
+        assert_eq!(names, vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn blocking_validate() {
+        let catalog = Catalog::new(
+            strings(&["Modern"]),
+            strings(&["Creature"]),
+            strings(&["Goblin"]),
+            strings(&["Legendary"]),
+        );
+        let client = Client::with_transport(Fake::new(&[(
+            "/v1/cards/?subtypes=Goblin",
+            &page(&["Goblin Guide"]),
+        )]));
+        let (filter, unknown) = cards::filter()
+            .client(&client)
+            .subtypes("goblin")
+            .validate(&catalog, Validation::Reject)
+            .unwrap();
+        assert!(unknown.is_empty());
+        assert_eq!(filter.all().unwrap()[0].name, "Goblin Guide");
+
+        let unknown = cards::filter()
+            .subtypes("goblinn")
+            .validate(&catalog, Validation::Reject)
+            .unwrap_err();
+        assert_eq!(unknown[0].suggestion.as_deref(), Some("Goblin"));
+    }
+}
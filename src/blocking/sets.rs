@@ -0,0 +1,110 @@
+//! Get game sets (e.g.: Urza's Saga, Darksteel, Dragons of Tarkir).
+//!
+//! Blocking version of `crate::sets`. The filters are the same, see `crate::sets::filter()`.
+use crate::client::Client;
+use crate::ids::SetCode;
+use crate::sets::{self, Set};
+use futures::executor::block_on;
+use http::StatusCode;
+
+/// Function to get all sets.
+///
+/// # Example
+/// ```rust,no_run
+/// use mtgsdk::blocking::sets;
+/// let sets = sets::all();
+/// assert_eq!(sets.unwrap().get(0).unwrap().type_field, "core");
+///```
+///
+/// # Errors
+/// If the call fails, it will return a `Err(StatusCode)`.
+/// To see the possible return values, check the [API docs](https://docs.magicthegathering.io/#documentationerrors).
+pub fn all() -> Result<Vec<Set>, StatusCode> {
+    all_with(&super::client())
+}
+
+/// Function to get all sets with the given `Client` (see `crate::client`).
+///
+/// # Errors
+/// If the call fails, it will return a `Err(StatusCode)`.
+/// To see the possible return values, check the [API docs](https://docs.magicthegathering.io/#documentationerrors).
+pub fn all_with(client: &Client) -> Result<Vec<Set>, StatusCode> {
+    block_on(sets::all_with(client))
+}
+
+/// Function to get a single set, by its code (a `SetCode` or a `&str`, see `crate::ids`).
+///
+/// # Example
+/// ```rust,no_run
+/// use mtgsdk::blocking::sets;
+/// let sets = sets::find("dom");
+/// assert_eq!(sets.unwrap().name, "Dominaria");
+///```
+///
+/// # Errors
+/// If the call fails, it will return a `Err(StatusCode)`.
+/// To see the possible return values, check the [API docs](https://docs.magicthegathering.io/#documentationerrors).
+pub fn find<C: Into<SetCode>>(code: C) -> Result<Set, StatusCode> {
+    find_with(&super::client(), code)
+}
+
+/// Function to get a single set with the given `Client` (see `crate::client`).
+///
+/// # Errors
+/// If the call fails, it will return a `Err(StatusCode)`.
+/// To see the possible return values, check the [API docs](https://docs.magicthegathering.io/#documentationerrors).
+pub fn find_with<C: Into<SetCode>>(client: &Client, code: C) -> Result<Set, StatusCode> {
+    block_on(sets::find_with(client, code))
+}
+
+#[doc(hidden)]
+#[derive(Clone, Debug)]
+pub struct Where<'a>(sets::Where<'a>);
+
+/// Function to get all sets matching the query filters.
+///
+/// To use it, call `filter()` followed by the desired filters and then close with `all()`.
+///
+/// # Example
+/// ```rust,no_run
+/// use mtgsdk::blocking::sets;
+/// let sets = sets::filter()
+///    .block("kamigawa")
+///    .name("bet")
+///    .all();
+/// assert!(sets.unwrap().iter().any(|set| set.name == "Betrayers of Kamigawa"));
+///```
+///
+/// # Errors
+/// If the call fails, it will return a `Err(StatusCode)`.
+/// To see the possible return values, check the [API docs](https://docs.magicthegathering.io/#documentationerrors).
+pub fn filter<'a>() -> Where<'a> {
+    Where(sets::filter().client(&super::client()))
+}
+
+impl<'a> Where<'a> {
+    /// Makes the call with the given `Client` instead of the default one (see `crate::client`).
+    pub fn client(self, client: &Client) -> Self {
+        Where(self.0.client(client))
+    }
+
+    pub fn name(self, input: &'a str) -> Self {
+        Where(self.0.name(input))
+    }
+
+    pub fn block(self, input: &'a str) -> Self {
+        Where(self.0.block(input))
+    }
+
+    pub fn page(self, input: u64) -> Self {
+        Where(self.0.page(input))
+    }
+
+    pub fn page_size(self, input: u64) -> Self {
+        Where(self.0.page_size(input))
+    }
+
+    pub fn all(self) -> Result<Vec<Set>, StatusCode> {
+        block_on(self.0.all())
+    }
+}
@@ -0,0 +1,33 @@
+//! Get card sub types (e.g.: Adventure, Elemental, Trap, Wizard).
+//!
+//! Blocking version of `crate::subtypes`.
+use crate::client::Client;
+use crate::subtypes;
+use futures::executor::block_on;
+use http::StatusCode;
+use std::collections::HashSet;
+
+/// Function to get all sub types.
+///
+/// # Example
+/// ```rust,no_run
+/// use mtgsdk::blocking::subtypes;
+/// let subtypes = subtypes::all();
+/// assert!(subtypes.unwrap().contains("Planeswalker"));
+///```
+///
+/// # Errors
+/// If the call fails, it will return a `Err(StatusCode)`.
+/// To see the possible return values, check the [API docs](https://docs.magicthegathering.io/#documentationerrors).
+pub fn all() -> Result<HashSet<String>, StatusCode> {
+    all_with(&super::client())
+}
+
+/// Function to get all subtypes with the given `Client` (see `crate::client`).
+///
+/// # Errors
+/// If the call fails, it will return a `Err(StatusCode)`.
+/// To see the possible return values, check the [API docs](https://docs.magicthegathering.io/#documentationerrors).
+pub fn all_with(client: &Client) -> Result<HashSet<String>, StatusCode> {
+    block_on(subtypes::all_with(client))
+}
@@ -0,0 +1,33 @@
+//! Get card super types (e.g.: Basic, Legendary, Snow).
+//!
+//! Blocking version of `crate::supertypes`.
+use crate::client::Client;
+use crate::supertypes;
+use futures::executor::block_on;
+use http::StatusCode;
+use std::collections::HashSet;
+
+/// Function to get all super types.
+///
+/// # Example
+/// ```rust,no_run
+/// use mtgsdk::blocking::supertypes;
+/// let supertypes = supertypes::all();
+/// assert!(supertypes.unwrap().contains("Basic"));
+///```
+///
+/// # Errors
+/// If the call fails, it will return a `Err(StatusCode)`.
+/// To see the possible return values, check the [API docs](https://docs.magicthegathering.io/#documentationerrors).
+pub fn all() -> Result<HashSet<String>, StatusCode> {
+    all_with(&super::client())
+}
+
+/// Function to get all supertypes with the given `Client` (see `crate::client`).
+///
+/// # Errors
+/// If the call fails, it will return a `Err(StatusCode)`.
+/// To see the possible return values, check the [API docs](https://docs.magicthegathering.io/#documentationerrors).
+pub fn all_with(client: &Client) -> Result<HashSet<String>, StatusCode> {
+    block_on(supertypes::all_with(client))
+}
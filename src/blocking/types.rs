@@ -0,0 +1,33 @@
+//! Get card types (e.g.: Artifact, Land, Sorcery).
+//!
+//! Blocking version of `crate::types`.
+use crate::client::Client;
+use crate::types;
+use futures::executor::block_on;
+use http::StatusCode;
+use std::collections::HashSet;
+
+/// Function to get all types.
+///
+/// # Example
+/// ```rust,no_run
+/// use mtgsdk::blocking::types;
+/// let types = types::all();
+/// assert!(types.unwrap().contains("Planeswalker"));
+///```
+///
+/// # Errors
+/// If the call fails, it will return a `Err(StatusCode)`.
+/// To see the possible return values, check the [API docs](https://docs.magicthegathering.io/#documentationerrors).
+pub fn all() -> Result<HashSet<String>, StatusCode> {
+    all_with(&super::client())
+}
+
+/// Function to get all types with the given `Client` (see `crate::client`).
+///
+/// # Errors
+/// If the call fails, it will return a `Err(StatusCode)`.
+/// To see the possible return values, check the [API docs](https://docs.magicthegathering.io/#documentationerrors).
+pub fn all_with(client: &Client) -> Result<HashSet<String>, StatusCode> {
+    block_on(types::all_with(client))
+}
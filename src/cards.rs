@@ -3,10 +3,17 @@
 //! Alongside `sets`, `cards' is one of the calls that allow the `find()` method as well as specific filters.
 //! For a complete list of the paremeters available for the filters, check de [API docs](https://docs.magicthegathering.io/#api_v1cards_list).
 #![allow(dead_code)]
+use crate::catalog::{Catalog, UnknownValue, Validation};
+use crate::client::Client;
+use crate::date::{self, Date};
+use crate::ids::{CardId, MultiverseId, SetCode};
 use crate::query_builder;
-use reqwest::StatusCode;
+use crate::select::CardFilter;
+use crate::stats::StatValue;
+use futures::stream::{self, Stream, StreamExt};
+use http::{HeaderMap, StatusCode};
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 /// Structure to deserialize rulings inside the cards' JSON.
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
@@ -26,7 +33,7 @@ pub struct ForeignNames {
     pub flavor: Option<String>,
     pub image_url: Option<String>,
     pub language: String,
-    pub multiverseid: Option<i64>,
+    pub multiverseid: Option<MultiverseId>,
 }
 
 /// Structure to deserialize legalities inside the cards' JSON.
@@ -36,12 +43,11 @@ pub struct Legalities {
     pub legality: String,
 }
 
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-
 /// Structure to deserialize cards' JSON.
 ///
 /// Values inside `Option` are optional, and you should check if there is `Some` or `None` before using it.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Card {
     pub name: String,
     pub layout: String,
@@ -55,7 +61,7 @@ pub struct Card {
     pub subtypes: Option<HashSet<String>>,
     pub rarity: String,
     #[serde(rename = "set")]
-    pub set_field: String,
+    pub set_field: SetCode,
     pub set_name: String,
     #[serde(default)]
     pub text: String,
@@ -66,35 +72,115 @@ pub struct Card {
     pub toughness: Option<String>,
     pub loyalty: Option<String>,
     // pub language: String,
-    pub id: String,
-    pub multiverseid: Option<String>,
+    pub id: CardId,
+    pub multiverseid: Option<MultiverseId>,
     pub names: Option<HashSet<String>>,
     pub mana_cost: Option<String>,
     pub variations: Option<Vec<String>>,
     pub image_url: Option<String>,
     pub watermark: Option<String>,
     pub border: Option<String>,
-    pub release_date: Option<String>,
+    #[serde(default, deserialize_with = "date::lenient")]
+    pub release_date: Option<Date>,
     pub rulings: Option<Vec<Rulings>>,
     #[serde(default)]
     pub foreign_names: Vec<ForeignNames>,
-    pub printings: HashSet<String>,
+    pub printings: HashSet<SetCode>,
     pub original_text: Option<String>,
     pub original_type: Option<String>,
     #[serde(default)]
     pub legalities: Vec<Legalities>,
 }
 
+// Colors are named in full by the API ("Blue") and by their mana symbol elsewhere ("U")
+const COLORS: [(&str, &str); 5] = [
+    ("W", "White"),
+    ("U", "Blue"),
+    ("B", "Black"),
+    ("R", "Red"),
+    ("G", "Green"),
+];
+
+pub(crate) fn color_name(symbol: &str) -> Option<&'static str> {
+    COLORS
+        .iter()
+        .find(|(s, _)| s.eq_ignore_ascii_case(symbol))
+        .map(|(_, name)| *name)
+}
+
+pub(crate) fn color_symbol(name: &str) -> Option<&'static str> {
+    COLORS
+        .iter()
+        .find(|(_, n)| n.eq_ignore_ascii_case(name))
+        .map(|(symbol, _)| *symbol)
+}
+
+// The API names formats with a capital letter ("Modern"), other sources use lowercase keys ("modern")
+pub(crate) fn format_name(key: &str) -> String {
+    let mut chars = key.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+// Gatherer image URL, as returned by the API in `Card::image_url`
+pub(crate) fn gatherer_image_url(multiverseid: MultiverseId) -> String {
+    format!(
+        "http://gatherer.wizards.com/Handlers/Image.ashx?multiverseid={}&type=card",
+        multiverseid
+    )
+}
+
+/// Rarity of a card, lowest first (e.g.: for comparisons, `Rarity::Rare < Rarity::Mythic`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Rarity {
+    Common,
+    Uncommon,
+    Rare,
+    Mythic,
+    Special,
+}
+
+// Rarities as the API names them, with their short names
+const RARITIES: [(Rarity, &str, &[&str]); 5] = [
+    (Rarity::Common, "Common", &["c"]),
+    (Rarity::Uncommon, "Uncommon", &["u"]),
+    (Rarity::Rare, "Rare", &["r"]),
+    (Rarity::Mythic, "Mythic Rare", &["m", "mythic"]),
+    (Rarity::Special, "Special", &["s"]),
+];
+
+impl Rarity {
+    /// Reads a rarity as named by the API ("Mythic Rare") or shortly ("mythic", "m"), ignoring case.
+    ///
+    /// Other rarities (e.g.: "Basic Land") give `None`.
+    pub fn parse(rarity: &str) -> Option<Rarity> {
+        let rarity = rarity.to_lowercase();
+        RARITIES
+            .iter()
+            .find(|(_, name, aliases)| {
+                name.to_lowercase() == rarity || aliases.contains(&rarity.as_str())
+            })
+            .map(|(r, _, _)| *r)
+    }
+
+    /// Name used by the API (e.g.: for the `rarity()` filter).
+    pub fn api_name(self) -> &'static str {
+        RARITIES.iter().find(|(r, _, _)| *r == self).unwrap().1
+    }
+}
+
 #[doc(hidden)]
 #[derive(Clone, Debug, Deserialize)]
 pub struct RootAll {
-    cards: Vec<Card>,
+    pub(crate) cards: Vec<Card>,
 }
 
 #[doc(hidden)]
 #[derive(Clone, Debug, Deserialize)]
 pub struct RootFind {
-    card: Card,
+    pub(crate) card: Card,
 }
 
 /// Function to get all cards.
@@ -118,7 +204,16 @@ pub struct RootFind {
 /// If the call fails, it will return a `Err(StatusCode)`.
 /// To see the possible return values, check the [API docs](https://docs.magicthegathering.io/#documentationerrors).
 pub async fn all() -> Result<Vec<Card>, StatusCode> {
-    let cards: Result<RootAll, StatusCode> = query_builder::all("cards").await;
+    all_with(&Client::default()).await
+}
+
+/// Function to get all cards with the given `Client` (see `client`).
+///
+/// # Errors
+/// If the call fails, it will return a `Err(StatusCode)`.
+/// To see the possible return values, check the [API docs](https://docs.magicthegathering.io/#documentationerrors).
+pub async fn all_with(client: &Client) -> Result<Vec<Card>, StatusCode> {
+    let cards: Result<RootAll, StatusCode> = query_builder::all(client, "cards").await;
 
     match cards {
         Ok(t) => Ok(t.cards),
@@ -128,21 +223,51 @@ pub async fn all() -> Result<Vec<Card>, StatusCode> {
 
 /// Function to get a single card.
 ///
+/// The card is found either by its `multiverseid` (a `MultiverseId`, or simply a `u64`) or by its `id` (a `CardId`).
+///
 /// # Example
 /// ```rust
 /// use mtgsdk::cards;
+/// use mtgsdk::ids::{CardId, MultiverseId};
 /// async {
 ///    let cards = cards::find(386616).await;
 ///    assert_eq!(cards.unwrap().name, "Narset, Enlightened Master");
+///
+///    let cards = cards::find(MultiverseId(386616)).await;
+///    assert_eq!(cards.unwrap().name, "Narset, Enlightened Master");
+///
+///    let id = CardId::parse("5f8287b1-5bb6-5f4f-ad17-316a40d5bb0c").unwrap();
+///    let cards = cards::find(id).await;
+///    assert!(cards.is_ok());
+/// };
+///```
+///
+/// # Errors
+/// If the call fails, it will return a `Err(StatusCode)`.
+/// To see the possible return values, check the [API docs](https://docs.magicthegathering.io/#documentationerrors).
+pub async fn find<K: Into<CardKey>>(id: K) -> Result<Card, StatusCode> {
+    find_with(&Client::default(), id).await
+}
+
+/// Function to get a single card with the given `Client` (see `client`).
+///
+/// # Example
+/// ```rust
+/// use mtgsdk::cards;
+/// use mtgsdk::client::Client;
+/// async {
+///    let client = Client::default();
+///    let cards = cards::find_with(&client, 386616).await;
+///    assert_eq!(cards.unwrap().name, "Narset, Enlightened Master");
 /// };
 ///```
 ///
 /// # Errors
 /// If the call fails, it will return a `Err(StatusCode)`.
 /// To see the possible return values, check the [API docs](https://docs.magicthegathering.io/#documentationerrors).
-pub async fn find(id: u64) -> Result<Card, StatusCode> {
-    let text_id = id.to_string();
-    let cards: Result<RootFind, StatusCode> = query_builder::find("cards", &text_id).await;
+pub async fn find_with<K: Into<CardKey>>(client: &Client, id: K) -> Result<Card, StatusCode> {
+    let text_id = id.into().value();
+    let cards: Result<RootFind, StatusCode> = query_builder::find(client, "cards", &text_id).await;
 
     match cards {
         Ok(t) => Ok(t.card),
@@ -150,9 +275,241 @@ pub async fn find(id: u64) -> Result<Card, StatusCode> {
     }
 }
 
+/// Function to get a card by its exact name.
+///
+/// The `name()` filter matches any card containing the text, `find_by_name()` only the cards named exactly `name`
+/// (case insensitive). As a card may be printed in many sets, `set` picks the printing; otherwise the first
+/// printing returned by the API is used.
+///
+/// # Example
+/// ```rust
+/// use mtgsdk::cards;
+/// async {
+///    let card = cards::find_by_name("Fire", Some("APC")).await;
+///    assert_eq!(card.unwrap().set_field, "APC");
+///
+///    let card = cards::find_by_name("Island", None).await;
+///    assert_eq!(card.unwrap().name, "Island");
+/// };
+///```
+///
+/// # Errors
+/// If there is no such card (in the set, if any), it will return a `Err(StatusCode::NOT_FOUND)`.
+/// If the call fails, it will return a `Err(StatusCode)`.
+/// To see the possible return values, check the [API docs](https://docs.magicthegathering.io/#documentationerrors).
+pub async fn find_by_name(name: &str, set: Option<&str>) -> Result<Card, StatusCode> {
+    find_by_name_with(&Client::default(), name, set).await
+}
+
+/// Function to get a card by its exact name with the given `Client` (see `find_by_name()` and `client`).
+///
+/// # Errors
+/// If there is no such card (in the set, if any), it will return a `Err(StatusCode::NOT_FOUND)`.
+/// If the call fails, it will return a `Err(StatusCode)`.
+/// To see the possible return values, check the [API docs](https://docs.magicthegathering.io/#documentationerrors).
+pub async fn find_by_name_with(
+    client: &Client,
+    name: &str,
+    set: Option<&str>,
+) -> Result<Card, StatusCode> {
+    let cards = by_name(name, set).client(client).stream();
+    let mut cards = Box::pin(cards);
+
+    while let Some(card) = cards.next().await {
+        let card = card?;
+        if is_printing(&card, name, set) {
+            return Ok(card);
+        }
+    }
+    Err(StatusCode::NOT_FOUND)
+}
+
+// The API matches a quoted name exactly
+pub(crate) fn by_name<'a>(name: &str, set: Option<&'a str>) -> Where<'a> {
+    let filter = filter().raw_filter("name", format!("\"{}\"", name));
+    match set {
+        Some(set) => filter.set_field(set),
+        None => filter,
+    }
+}
+
+pub(crate) fn is_printing(card: &Card, name: &str, set: Option<&str>) -> bool {
+    card.name.eq_ignore_ascii_case(name)
+        && set.is_none_or(|set| card.set_field.eq_ignore_ascii_case(set))
+}
+
+// Longest URL sent by `find_many()`, below the usual server limits
+const FIND_MANY_URL_LEN: usize = 2000;
+// Most ids in a single `find_many()` request
+const FIND_MANY_BATCH: usize = 50;
+// Requests of `find_many()` sent at the same time
+const FIND_MANY_IN_FLIGHT: usize = 4;
+// Retries of a page of `fetch_all_concurrent()` refused by the rate limit
+const RATE_LIMIT_RETRIES: u32 = 5;
+
+/// Card identifier for `find()` and `find_many()`: either the card's `id` or its `multiverseid`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum CardKey {
+    Id(CardId),
+    Multiverse(MultiverseId),
+}
+
+impl From<CardId> for CardKey {
+    fn from(id: CardId) -> Self {
+        CardKey::Id(id)
+    }
+}
+
+impl From<MultiverseId> for CardKey {
+    fn from(id: MultiverseId) -> Self {
+        CardKey::Multiverse(id)
+    }
+}
+
+impl From<u64> for CardKey {
+    fn from(id: u64) -> Self {
+        CardKey::Multiverse(MultiverseId(id))
+    }
+}
+
+impl CardKey {
+    fn filter_key(&self) -> &'static str {
+        match self {
+            CardKey::Id(_) => "id",
+            CardKey::Multiverse(_) => "multiverseid",
+        }
+    }
+
+    pub(crate) fn value(&self) -> String {
+        match self {
+            CardKey::Id(id) => id.to_string(),
+            CardKey::Multiverse(id) => id.to_string(),
+        }
+    }
+
+    pub(crate) fn matches(&self, card: &Card) -> bool {
+        match self {
+            CardKey::Id(id) => card.id == *id,
+            CardKey::Multiverse(id) => card.multiverseid == Some(*id),
+        }
+    }
+}
+
+/// Function to get many cards at once, by `id` and/or `multiverseid`.
+///
+/// The ids are grouped into a few filter calls (ids joined with `|`, kept under the URL length limits),
+/// which are fetched concurrently.
+///
+/// Every id is in the returned map: the cards that do not exist are `Err(StatusCode::NOT_FOUND)`.
+/// When an id matches more than one card (e.g.: the halves of a split card share their `multiverseid`),
+/// the first one is returned, as in `find()`.
+///
+/// # Example
+/// ```rust
+/// use mtgsdk::cards::{self, CardKey};
+/// async {
+///    let cards = cards::find_many(vec![CardKey::from(386616), CardKey::from(409574)]).await;
+///    assert_eq!(cards[&CardKey::from(386616)].as_ref().unwrap().name, "Narset, Enlightened Master");
+/// };
+///```
+///
+/// # Errors
+/// If a call fails, its ids not found in the pages fetched will be `Err(StatusCode)`.
+/// To see the possible return values, check the [API docs](https://docs.magicthegathering.io/#documentationerrors).
+pub async fn find_many<I>(ids: I) -> HashMap<CardKey, Result<Card, StatusCode>>
+where
+    I: IntoIterator,
+    I::Item: Into<CardKey>,
+{
+    find_many_with(&Client::default(), ids).await
+}
+
+/// Function to get many cards at once with the given `Client` (see `find_many()` and `client`).
+///
+/// # Errors
+/// If a call fails, its ids not found in the pages fetched will be `Err(StatusCode)`.
+/// To see the possible return values, check the [API docs](https://docs.magicthegathering.io/#documentationerrors).
+pub async fn find_many_with<I>(
+    client: &Client,
+    ids: I,
+) -> HashMap<CardKey, Result<Card, StatusCode>>
+where
+    I: IntoIterator,
+    I::Item: Into<CardKey>,
+{
+    let mut keys: Vec<CardKey> = ids.into_iter().map(Into::into).collect();
+    keys.sort();
+    keys.dedup();
+
+    let batches = find_many_batches(keys);
+    let results: Vec<(Vec<CardKey>, _)> = stream::iter(batches)
+        .map(|batch| async move {
+            let values: Vec<String> = batch.iter().map(CardKey::value).collect();
+            let cards = filter()
+                .client(client)
+                .raw_filter(batch[0].filter_key(), values.join("|"))
+                .page_size(100)
+                .fetch_pages_concurrent(1)
+                .await;
+            (batch, cards)
+        })
+        .buffer_unordered(FIND_MANY_IN_FLIGHT)
+        .collect()
+        .await;
+
+    // The cards of the pages fetched are kept: only the ids left without one get the error of a failed page
+    let mut found = HashMap::new();
+    for (batch, (cards, errors)) in results {
+        for key in batch {
+            let card = match cards.iter().find(|card| key.matches(card)) {
+                Some(card) => Ok(card.clone()),
+                None => Err(errors.first().map_or(StatusCode::NOT_FOUND, |(_, e)| *e)),
+            };
+            found.insert(key, card);
+        }
+    }
+    found
+}
+
+// Group sorted keys of the same kind, within the URL length and batch size limits
+fn find_many_batches(keys: Vec<CardKey>) -> Vec<Vec<CardKey>> {
+    let base = format!(
+        "{}/{}/cards/?multiverseid=&pageSize=100&page=100",
+        query_builder::API_URL,
+        query_builder::API_VER
+    )
+    .len();
+
+    let mut batches: Vec<Vec<CardKey>> = Vec::new();
+    let mut length = 0;
+    for key in keys {
+        let added = key.value().len() + 1;
+        let fits = match batches.last() {
+            Some(batch) => {
+                batch[0].filter_key() == key.filter_key()
+                    && batch.len() < FIND_MANY_BATCH
+                    && base + length + added <= FIND_MANY_URL_LEN
+            }
+            None => false,
+        };
+        if fits {
+            length += added;
+            batches.last_mut().unwrap().push(key);
+        } else {
+            length = added;
+            batches.push(vec![key]);
+        }
+    }
+    batches
+}
+
 #[doc(hidden)]
+#[derive(Clone, Debug)]
 pub struct Where<'a> {
     query: Vec<(&'a str, String)>,
+    client: Option<Client>,
+    // Conditions the API can't ask for (e.g.: a power range), checked on the fetched cards
+    select: Option<CardFilter>,
 }
 
 /// Function to get all card matching the query filters.
@@ -191,10 +548,20 @@ pub struct Where<'a> {
 /// If the call fails, it will return a `Err(StatusCode)`.
 /// To see the possible return values, check the [API docs](https://docs.magicthegathering.io/#documentationerrors).
 pub fn filter<'a>() -> Where<'a> {
-    Where { query: Vec::new() }
+    Where {
+        query: Vec::new(),
+        client: None,
+        select: None,
+    }
 }
 
 impl<'a> Where<'a> {
+    /// Makes the call with the given `Client` instead of the default one (see `client`).
+    pub fn client(mut self, client: &Client) -> Self {
+        self.client = Some(client.clone());
+        self
+    }
+
     pub fn name(mut self, input: &'a str) -> Self {
         self.query.push(("name", String::from(input)));
         self
@@ -267,6 +634,34 @@ impl<'a> Where<'a> {
         self.query.push(("toughness", String::from(input)));
         self
     }
+    /// Cards with exactly this power (e.g.: `StatValue::from(3)`, `"1+*".parse().unwrap()`).
+    pub fn power_value(self, value: StatValue) -> Self {
+        self.raw_filter("power", value.to_string())
+    }
+    /// Cards with a power of at least `min`, as ordered by `StatValue` (e.g.: "*" counts as 0, "∞" is above every
+    /// number).
+    ///
+    /// The API has no ranges: the cards are checked once fetched (as with `select::CardFilter::power()`), so
+    /// the other filters should narrow the search.
+    pub fn power_at_least(self, min: i64) -> Self {
+        self.select(CardFilter::power().ge(StatValue::from(min)))
+    }
+    /// Cards with a power of at most `max`, as ordered by `StatValue` (see `power_at_least()`).
+    pub fn power_at_most(self, max: i64) -> Self {
+        self.select(CardFilter::power().le(StatValue::from(max)))
+    }
+    /// Cards with exactly this toughness (e.g.: `StatValue::from(3)`, `"1+*".parse().unwrap()`).
+    pub fn toughness_value(self, value: StatValue) -> Self {
+        self.raw_filter("toughness", value.to_string())
+    }
+    /// Cards with a toughness of at least `min`, as ordered by `StatValue` (see `power_at_least()`).
+    pub fn toughness_at_least(self, min: i64) -> Self {
+        self.select(CardFilter::toughness().ge(StatValue::from(min)))
+    }
+    /// Cards with a toughness of at most `max`, as ordered by `StatValue` (see `power_at_least()`).
+    pub fn toughness_at_most(self, max: i64) -> Self {
+        self.select(CardFilter::toughness().le(StatValue::from(max)))
+    }
     pub fn loyalty(mut self, input: &'a str) -> Self {
         self.query.push(("loyalty", String::from(input)));
         self
@@ -312,19 +707,645 @@ impl<'a> Where<'a> {
         self
     }
 
-    pub async fn all(mut self) -> Result<Vec<Card>, StatusCode> {
-        let val = self.query.remove(0);
-        let mut filter = format!("?{}={}", val.0, val.1);
+    /// Checks the `types()`, `subtypes()`, `supertypes()` and `game_format()` values against the catalog,
+    /// writing the known ones as the API does (e.g.: "legendary" is "Legendary").
+    ///
+    /// # Example
+    /// ```rust
+    /// use mtgsdk::cards;
+    /// use mtgsdk::catalog::{Catalog, Validation};
+    /// async {
+    ///     let catalog = Catalog::fetch().await.unwrap();
+    ///     let filter = cards::filter().subtypes("goblinn").validate(&catalog, Validation::Reject);
+    ///     assert_eq!(filter.unwrap_err()[0].suggestion.as_deref(), Some("Goblin"));
+    /// };
+    ///```
+    ///
+    /// Returns the filter with the unknown values (always none with `Validation::Reject`).
+    ///
+    /// # Errors
+    /// With `Validation::Reject`, if any value is unknown, it will return a `Err(Vec<UnknownValue>)`.
+    pub fn validate(
+        mut self,
+        catalog: &Catalog,
+        validation: Validation,
+    ) -> Result<(Self, Vec<UnknownValue>), Vec<UnknownValue>> {
+        let mut unknown = Vec::new();
+        for (key, value) in self.query.iter_mut() {
+            let (canonical, missing) = catalog.check(key, value);
+            *value = canonical;
+            unknown.extend(missing);
+        }
+
+        match validation {
+            Validation::Reject if !unknown.is_empty() => Err(unknown),
+            _ => Ok((self, unknown)),
+        }
+    }
+
+    // Build the query string (?param=value&param=value)
+    pub(crate) fn query_string(&self) -> String {
+        query_builder::query_string(&self.query)
+    }
+
+    // Filter with an already formatted value (e.g.: values joined with `|`, a quoted name)
+    pub(crate) fn raw_filter(mut self, key: &'a str, value: String) -> Self {
+        self.query.push((key, value));
+        self
+    }
+
+    // Add a condition checked on the fetched cards
+    fn select(mut self, condition: CardFilter) -> Self {
+        self.select = Some(match self.select.take() {
+            Some(select) => select.and(condition),
+            None => condition,
+        });
+        self
+    }
+
+    // The conditions checked on the fetched cards (every card if none)
+    fn selection(&self) -> CardFilter {
+        self.select.clone().unwrap_or_else(CardFilter::any)
+    }
+
+    // Value of a numeric filter (e.g.: `page`)
+    fn numeric_filter(&self, key: &str) -> Option<u64> {
+        self.query
+            .iter()
+            .rev()
+            .find(|(k, _)| *k == key)
+            .and_then(|(_, v)| v.parse().ok())
+    }
+
+    // One filter per page, from the `page()` filter (or the first page) onwards
+    pub(crate) fn pages(self) -> impl Iterator<Item = Where<'a>> {
+        let first = self.numeric_filter("page").unwrap_or(1);
+        let query: Vec<(&'a str, String)> = self
+            .query
+            .into_iter()
+            .filter(|(k, _)| *k != "page")
+            .collect();
+        let client = self.client;
+
+        (first..).map(move |page| {
+            Where {
+                query: query.clone(),
+                client: client.clone(),
+                select: None,
+            }
+            .page(page)
+        })
+    }
+
+    pub async fn all(self) -> Result<Vec<Card>, StatusCode> {
+        let client = self.client.clone().unwrap_or_default();
+        let cards: Result<RootAll, StatusCode> =
+            query_builder::filter(&client, "cards", &self.query_string()).await;
+
+        match cards {
+            Ok(t) => Ok(self.selection().apply(t.cards)),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Returns every card matching the filters, one at a time, fetching the pages as needed.
+    ///
+    /// Pagination starts at the `page()` filter (or the first page) and ends with the last page: the first one shorter
+    /// than the page size, or the one reaching the `Total-Count` header.
+    /// If a page fails, its error is the last item of the stream.
+    ///
+    /// # Example
+    /// ```rust
+    /// use futures::StreamExt;
+    /// use mtgsdk::cards;
+    /// async {
+    ///     let cards: Vec<_> = cards::filter()
+    ///         .set_field("KTK")
+    ///         .stream()
+    ///         .collect()
+    ///         .await;
+    ///     assert!(cards.into_iter().all(|card| card.is_ok()));
+    /// };
+    ///```
+    pub fn stream(self) -> impl Stream<Item = Result<Card, StatusCode>> + 'a {
+        let client = self.client.clone().unwrap_or_default();
+        let first = self.numeric_filter("page").unwrap_or(1);
+        let page_size = self.numeric_filter("pageSize").unwrap_or(100).clamp(1, 100);
+        let selection = self.selection();
+        let state = Some((self.pages(), first, client));
+
+        let pages = stream::unfold(state, move |state| async move {
+            let (mut pages, number, client) = state?;
+            let filter = pages.next()?;
+            match filter.page_with_headers(&client).await {
+                Ok((cards, headers)) => {
+                    let last = (cards.len() as u64) < page_size
+                        || header_number(&headers, "total-count")
+                            .is_some_and(|total| number * page_size >= total);
+                    let next = if last {
+                        None
+                    } else {
+                        Some((pages, number + 1, client))
+                    };
+                    Some((Ok(cards), next))
+                }
+                Err(e) => Some((Err(e), None)),
+            }
+        });
+
+        let cards = pages.flat_map(|page| {
+            let cards: Vec<Result<Card, StatusCode>> = match page {
+                Ok(cards) => cards.into_iter().map(Ok).collect(),
+                Err(e) => vec![Err(e)],
+            };
+            stream::iter(cards)
+        });
+        selection.stream(cards)
+    }
+
+    /// Returns every card matching the filters, fetching up to `max_in_flight` pages at the same time.
+    ///
+    /// The first page (from the `page()` filter, or the first page) is fetched alone: its `Total-Count` header gives
+    /// the remaining pages, and its `Ratelimit-Remaining` header further limits how many are fetched at once.
+    /// The cards keep the order of the pages. A page refused with `429 Too Many Requests` is retried (up to 5 times)
+    /// after the delay of its `Retry-After` or `Ratelimit-Reset` header, or else an exponential backoff (from 1 second,
+    /// up to a minute). Without `Total-Count`, the pages are fetched one at a time until one is shorter than
+    /// the page size.
+    ///
+    /// # Example
+    /// ```rust
+    /// use mtgsdk::cards;
+    /// async {
+    ///     let cards = cards::filter()
+    ///         .set_field("KTK")
+    ///         .fetch_all_concurrent(4)
+    ///         .await;
+    ///     assert!(cards.unwrap().len() > 100);
+    /// };
+    ///```
+    ///
+    /// # Errors
+    /// If any page fails, it will return a `Err(Vec<(u64, StatusCode)>)` with every failed page and its status.
+    pub async fn fetch_all_concurrent(
+        self,
+        max_in_flight: usize,
+    ) -> Result<Vec<Card>, Vec<(u64, StatusCode)>> {
+        let (cards, errors) = self.fetch_pages_concurrent(max_in_flight).await;
+        if errors.is_empty() {
+            Ok(cards)
+        } else {
+            Err(errors)
+        }
+    }
+
+    // Pages of `fetch_all_concurrent()`: the cards of the pages fetched, and the pages that failed
+    pub(crate) async fn fetch_pages_concurrent(
+        self,
+        max_in_flight: usize,
+    ) -> (Vec<Card>, Vec<(u64, StatusCode)>) {
+        let client = self.client.clone().unwrap_or_default();
+        let first = self.numeric_filter("page").unwrap_or(1);
+        let page_size = self.numeric_filter("pageSize").unwrap_or(100).clamp(1, 100);
+        let selection = self.selection();
+        let mut pages = self.pages();
+
+        let (mut cards, headers) = match pages.next().unwrap().page_with_headers(&client).await {
+            Ok(page) => page,
+            Err(e) => return (Vec::new(), vec![(first, e)]),
+        };
+
+        // Without Total-Count, there is no page range to split: go one page at a time
+        let total = match header_number(&headers, "total-count") {
+            Some(total) => total,
+            None => {
+                let mut last = (cards.len() as u64) < page_size;
+                for (number, filter) in (first + 1..).zip(pages) {
+                    if last {
+                        break;
+                    }
+                    match filter.page_with_headers(&client).await {
+                        Ok((page, _)) => {
+                            last = (page.len() as u64) < page_size;
+                            cards.extend(page);
+                        }
+                        Err(e) => return (selection.apply(cards), vec![(number, e)]),
+                    }
+                }
+                return (selection.apply(cards), Vec::new());
+            }
+        };
 
-        for (k, v) in self.query.into_iter() {
-            filter = format!("{}&{}={}", filter, k, v);
+        let last = total.div_ceil(page_size);
+        let mut in_flight = max_in_flight.max(1);
+        if let Some(remaining) = header_number(&headers, "ratelimit-remaining") {
+            in_flight = in_flight.min(remaining.max(1) as usize);
         }
 
-        let cards: Result<RootAll, StatusCode> = query_builder::filter("cards", &filter).await;
+        let filters: Vec<(u64, Where<'a>)> = (first + 1..=last).zip(pages).collect();
+        let client = &client;
+        let results: Vec<Result<Vec<Card>, StatusCode>> = stream::iter(filters.iter().cloned())
+            .map(
+                |(_, filter)| async move { filter.page_with_headers(client).await.map(|(c, _)| c) },
+            )
+            .buffered(in_flight)
+            .collect()
+            .await;
+
+        let mut errors = Vec::new();
+        for (result, (number, _)) in results.into_iter().zip(filters.iter()) {
+            match result {
+                Ok(page) => cards.extend(page),
+                Err(e) => errors.push((*number, e)),
+            }
+        }
+        (selection.apply(cards), errors)
+    }
+
+    // Fetch a single page, keeping the response headers, and retry it when refused by the rate limit
+    async fn page_with_headers(
+        self,
+        client: &Client,
+    ) -> Result<(Vec<Card>, HeaderMap), StatusCode> {
+        let cards: Result<(RootAll, HeaderMap), StatusCode> = query_builder::filter_retrying(
+            client,
+            "cards",
+            &self.query_string(),
+            RATE_LIMIT_RETRIES,
+        )
+        .await;
 
         match cards {
-            Ok(t) => Ok(t.cards),
+            Ok((t, headers)) => Ok((t.cards, headers)),
             Err(e) => Err(e),
         }
     }
 }
+
+// Numeric value of a response header (e.g.: Total-Count)
+fn header_number(headers: &HeaderMap, name: &str) -> Option<u64> {
+    headers.get(name)?.to_str().ok()?.trim().parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cards;
+    use crate::client::tests::Fake;
+    use crate::client::Client;
+    use crate::fixtures::{self, page};
+    use crate::ids::{CardId, MultiverseId, SetCode};
+    use futures::executor::block_on;
+    use futures::StreamExt;
+    use http::StatusCode;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    #[test]
+    fn find_by_card_id() {
+        let id = CardId::parse("00000000-0000-0000-0000-00000000000a").unwrap();
+        let body = serde_json::json!({ "card": cards::Card { name: "a".to_string(), ..Default::default() } });
+        let fake = Fake::new(&[(
+            "/v1/cards/00000000-0000-0000-0000-00000000000a",
+            &body.to_string(),
+        )]);
+        let client = Client::with_transport(fake);
+        let card = block_on(cards::find_with(&client, id));
+        assert_eq!(card.unwrap().name, "a");
+    }
+
+    #[test]
+    fn find_by_name_exact() {
+        let body = serde_json::json!({ "cards": [
+            cards::Card { name: "Fire // Ice".to_string(), set_field: SetCode::parse("MH2").unwrap(), ..Default::default() },
+            cards::Card { name: "Ice".to_string(), set_field: SetCode::parse("APC").unwrap(), ..Default::default() },
+            cards::Card { name: "Ice".to_string(), set_field: SetCode::parse("MH2").unwrap(), ..Default::default() },
+        ] });
+        let fake = Arc::new(Fake::new(&[
+            ("/v1/cards/?name=%22Ice%22&page=1", &body.to_string()),
+            (
+                "/v1/cards/?name=%22ice%22&set=mh2&page=1",
+                &body.to_string(),
+            ),
+        ]));
+        let client = Client::with_transport(fake.clone());
+
+        let card = block_on(cards::find_by_name_with(&client, "Ice", None)).unwrap();
+        assert_eq!(card.set_field, "APC");
+        let card = block_on(cards::find_by_name_with(&client, "ice", Some("mh2"))).unwrap();
+        assert_eq!(
+            (card.name.as_str(), card.set_field.as_str()),
+            ("Ice", "MH2")
+        );
+        let card = block_on(cards::find_by_name_with(&client, "Ice", Some("KTK")));
+        assert_eq!(card.unwrap_err(), StatusCode::NOT_FOUND);
+
+        // A short first page is the last one
+        assert_eq!(
+            *fake.requests.lock().unwrap(),
+            vec![
+                "/v1/cards/?name=%22Ice%22&page=1",
+                "/v1/cards/?name=%22ice%22&set=mh2&page=1",
+                "/v1/cards/?name=%22Ice%22&set=KTK&page=1",
+            ]
+        );
+    }
+
+    #[test]
+    fn find_by_name_escaped() {
+        let name = "Minsc & Boo, Timeless Heroes";
+        let body = serde_json::json!({ "cards": [
+            cards::Card { name: name.to_string(), ..Default::default() },
+        ] });
+        let fake = Fake::new(&[(
+            "/v1/cards/?name=%22Minsc%20%26%20Boo,%20Timeless%20Heroes%22&page=1",
+            &body.to_string(),
+        )]);
+        let client = Client::with_transport(fake);
+        let card = block_on(cards::find_by_name_with(&client, name, None));
+        assert_eq!(card.unwrap().name, name);
+
+        let filter = cards::filter().text("+1/+1 counter").name("100%");
+        assert_eq!(
+            filter.query_string(),
+            "?text=%2B1%2F%2B1%20counter&name=100%25"
+        );
+    }
+
+    #[test]
+    fn find_many_batches() {
+        let mut keys: Vec<cards::CardKey> = (0..120u64).map(cards::CardKey::from).collect();
+        let id = CardId::parse("00000000-0000-0000-0000-00000000000a").unwrap();
+        keys.push(cards::CardKey::from(id.clone()));
+        keys.sort();
+        let batches = cards::find_many_batches(keys);
+        assert_eq!(batches.len(), 4);
+        assert_eq!(batches[0], vec![cards::CardKey::from(id)]);
+        assert!(batches.iter().all(|batch| batch.len() <= 50));
+    }
+
+    #[test]
+    fn stat_filters() {
+        let filter = cards::filter()
+            .set_field("mh1")
+            .power_at_least(2)
+            .toughness_at_most(4)
+            .toughness_value("1+*".parse().unwrap());
+        assert_eq!(filter.query_string(), "?set=mh1&toughness=1%2B*");
+
+        let card = |name: &str, power: &str, toughness: &str| cards::Card {
+            power: Some(power.to_string()),
+            toughness: Some(toughness.to_string()),
+            ..fixtures::card(name)
+        };
+        let cards = vec![
+            card("Tarmogoyf", "*", "1+*"),
+            card("Gaea's Herald", "1", "1"),
+            card("Little Girl", "½", "½"),
+            card("Ghalta", "12", "12"),
+            card("Dreadbore", "", ""),
+            card("Bonesplitter Sliver", "2", "2"),
+        ];
+        let body = serde_json::json!({ "cards": cards }).to_string();
+        let client = Client::with_transport(Fake::new(&[
+            ("/v1/cards/?set=mh1", &body),
+            ("/v1/cards/?set=mh1&page=1", &body),
+        ]));
+        let names = |cards: Vec<cards::Card>| -> Vec<String> {
+            cards.into_iter().map(|card| card.name).collect()
+        };
+
+        // "*" and "½" count as 0 and ½, as in `StatValue`: in range for "at most", out of it for "at least"
+        let at_most = cards::filter()
+            .client(&client)
+            .set_field("mh1")
+            .power_at_most(1);
+        assert_eq!(
+            names(block_on(at_most.all()).unwrap()),
+            vec!["Tarmogoyf", "Gaea's Herald", "Little Girl"]
+        );
+        let range = cards::filter()
+            .client(&client)
+            .set_field("mh1")
+            .power_at_least(0)
+            .toughness_at_most(2);
+        let streamed: Vec<_> = block_on(range.stream().collect());
+        let streamed = streamed.into_iter().collect::<Result<Vec<_>, _>>();
+        assert_eq!(
+            names(streamed.unwrap()),
+            vec![
+                "Tarmogoyf",
+                "Gaea's Herald",
+                "Little Girl",
+                "Bonesplitter Sliver"
+            ]
+        );
+        let at_least = cards::filter()
+            .client(&client)
+            .set_field("mh1")
+            .power_at_least(2);
+        assert_eq!(
+            names(block_on(at_least.fetch_all_concurrent(2)).unwrap()),
+            vec!["Ghalta", "Bonesplitter Sliver"]
+        );
+    }
+
+    #[test]
+    fn find_many_not_found() {
+        let id = CardId::parse("00000000-0000-0000-0000-00000000000a").unwrap();
+        let a = cards::Card {
+            name: "a".to_string(),
+            id: id.clone(),
+            multiverseid: Some(MultiverseId(1)),
+            ..Default::default()
+        };
+        let body = serde_json::json!({ "cards": [a] }).to_string();
+        let fake = Fake::new(&[
+            ("/v1/cards/?multiverseid=1|2&pageSize=100&page=1", &body),
+            (
+                "/v1/cards/?id=00000000-0000-0000-0000-00000000000a&pageSize=100&page=1",
+                &body,
+            ),
+        ])
+        .header("Total-Count", "1");
+        let client = Client::with_transport(fake);
+        let ids = vec![
+            cards::CardKey::from(2),
+            cards::CardKey::from(1),
+            cards::CardKey::from(id.clone()),
+            cards::CardKey::from(1),
+        ];
+        let found = block_on(cards::find_many_with(&client, ids));
+        assert_eq!(found.len(), 3);
+        assert_eq!(found[&cards::CardKey::from(1)].as_ref().unwrap().name, "a");
+        assert_eq!(found[&cards::CardKey::from(id)].as_ref().unwrap().name, "a");
+        assert_eq!(found[&cards::CardKey::from(2)], Err(StatusCode::NOT_FOUND));
+    }
+
+    #[test]
+    fn find_many_failed_page() {
+        let a = cards::Card {
+            multiverseid: Some(MultiverseId(1)),
+            ..fixtures::card("a")
+        };
+        let body = serde_json::json!({ "cards": [a] }).to_string();
+        // The batch has two pages: the first one is kept when the second one fails
+        let fake = Fake::new(&[("/v1/cards/?multiverseid=1|2|3&pageSize=100&page=1", &body)])
+            .status(
+                "/v1/cards/?multiverseid=1|2|3&pageSize=100&page=2",
+                StatusCode::SERVICE_UNAVAILABLE,
+            )
+            .header("Total-Count", "150");
+        let client = Client::with_transport(fake);
+        let ids: Vec<cards::CardKey> = (1..=3u64).map(cards::CardKey::from).collect();
+        let found = block_on(cards::find_many_with(&client, ids));
+        assert_eq!(found[&cards::CardKey::from(1)].as_ref().unwrap().name, "a");
+        assert_eq!(
+            found[&cards::CardKey::from(2)],
+            Err(StatusCode::SERVICE_UNAVAILABLE)
+        );
+        assert_eq!(
+            found[&cards::CardKey::from(3)],
+            Err(StatusCode::SERVICE_UNAVAILABLE)
+        );
+    }
+
+    #[test]
+    fn fetch_all_concurrent_in_order() {
+        let fake = Fake::new(&[
+            ("/v1/cards/?set=KTK&pageSize=2&page=1", &page(&["a", "b"])),
+            ("/v1/cards/?set=KTK&pageSize=2&page=2", &page(&["c", "d"])),
+            ("/v1/cards/?set=KTK&pageSize=2&page=3", &page(&["e"])),
+        ])
+        .header("Total-Count", "5");
+        let client = Client::with_transport(fake);
+        let cards = block_on(
+            cards::filter()
+                .client(&client)
+                .set_field("KTK")
+                .page_size(2)
+                .fetch_all_concurrent(2),
+        );
+        let names: Vec<String> = cards.unwrap().into_iter().map(|card| card.name).collect();
+        assert_eq!(names, vec!["a", "b", "c", "d", "e"]);
+    }
+
+    #[test]
+    fn fetch_all_concurrent_page_errors() {
+        let fake = Fake::new(&[
+            ("/v1/cards/?pageSize=2&page=1", &page(&["a", "b"])),
+            ("/v1/cards/?pageSize=2&page=3", &page(&["e"])),
+        ])
+        .status(
+            "/v1/cards/?pageSize=2&page=2",
+            StatusCode::INTERNAL_SERVER_ERROR,
+        )
+        .header("Total-Count", "5");
+        let client = Client::with_transport(fake);
+        let cards = block_on(
+            cards::filter()
+                .client(&client)
+                .page_size(2)
+                .fetch_all_concurrent(8),
+        );
+        assert_eq!(
+            cards.unwrap_err(),
+            vec![(2, StatusCode::INTERNAL_SERVER_ERROR)]
+        );
+    }
+
+    #[test]
+    fn fetch_all_concurrent_rate_limited() {
+        let fake = Arc::new(
+            Fake::new(&[
+                ("/v1/cards/?pageSize=2&page=1", &page(&["a", "b"])),
+                ("/v1/cards/?pageSize=2&page=2", &page(&["c"])),
+            ])
+            .status_once(
+                "/v1/cards/?pageSize=2&page=2",
+                StatusCode::TOO_MANY_REQUESTS,
+            )
+            .header("Total-Count", "3")
+            .header("Retry-After", "7"),
+        );
+        let client = Client::with_transport(fake.clone());
+        let cards = block_on(
+            cards::filter()
+                .client(&client)
+                .page_size(2)
+                .fetch_all_concurrent(4),
+        );
+        assert_eq!(cards.unwrap().len(), 3);
+        assert_eq!(*fake.sleeps.lock().unwrap(), vec![Duration::from_secs(7)]);
+        assert_eq!(fake.requests.lock().unwrap().len(), 3);
+
+        // Without Retry-After, the delay doubles at each refusal
+        let fake = Arc::new(
+            Fake::new(&[("/v1/cards/?page=1", &page(&["a"]))])
+                .status_once("/v1/cards/?page=1", StatusCode::TOO_MANY_REQUESTS)
+                .status_once("/v1/cards/?page=1", StatusCode::TOO_MANY_REQUESTS)
+                .header("Total-Count", "1"),
+        );
+        let client = Client::with_transport(fake.clone());
+        let cards = block_on(cards::filter().client(&client).fetch_all_concurrent(1));
+        assert_eq!(cards.unwrap().len(), 1);
+        assert_eq!(
+            *fake.sleeps.lock().unwrap(),
+            vec![Duration::from_secs(1), Duration::from_secs(2)]
+        );
+    }
+
+    #[test]
+    fn fetch_all_concurrent_without_total_count() {
+        let fake = Fake::new(&[
+            ("/v1/cards/?pageSize=2&page=1", &page(&["a", "b"])),
+            ("/v1/cards/?pageSize=2&page=2", &page(&["c"])),
+        ]);
+        let client = Client::with_transport(fake);
+        let cards = block_on(
+            cards::filter()
+                .client(&client)
+                .page_size(2)
+                .fetch_all_concurrent(4),
+        );
+        assert_eq!(cards.unwrap().len(), 3);
+    }
+
+    #[test]
+    fn stream_stops_at_last_page() {
+        // A page shorter than the page size is the last one
+        let fake = Arc::new(Fake::new(&[
+            ("/v1/cards/?pageSize=2&page=1", &page(&["a", "b"])),
+            ("/v1/cards/?pageSize=2&page=2", &page(&["c"])),
+        ]));
+        let client = Client::with_transport(fake.clone());
+        let cards: Vec<_> = block_on(
+            cards::filter()
+                .client(&client)
+                .page_size(2)
+                .stream()
+                .collect(),
+        );
+        assert_eq!(cards.len(), 3);
+        assert_eq!(fake.requests.lock().unwrap().len(), 2);
+
+        // A full page reaching Total-Count too
+        let fake = Arc::new(
+            Fake::new(&[
+                ("/v1/cards/?pageSize=2&page=2", &page(&["c", "d"])),
+                ("/v1/cards/?pageSize=2&page=3", &page(&["e", "f"])),
+            ])
+            .header("Total-Count", "6"),
+        );
+        let client = Client::with_transport(fake.clone());
+        let names: Vec<String> = block_on(
+            cards::filter()
+                .client(&client)
+                .page(2)
+                .page_size(2)
+                .stream()
+                .map(|card| card.unwrap().name)
+                .collect(),
+        );
+        assert_eq!(names, vec!["c", "d", "e", "f"]);
+        assert_eq!(fake.requests.lock().unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,398 @@
+//! The values the API knows for formats, types, subtypes and supertypes, fetched once and kept for a while.
+//!
+//! `formats::all()`, `types::all()`, `subtypes::all()` and `supertypes::all()` call the API every time.
+//! A `Catalog` holds the four of them (fetched at the same time), finds the canonical form of a value
+//! ("legendary" is "Legendary"), and checks the values of a `cards::filter()` before the call
+//! (see `cards::Where::validate()`), so that a typo does not silently return no cards.
+//!
+//! `CatalogCache` keeps a catalog for a given time (TTL), fetching it again once it is too old.
+//!
+//! # Example
+//! ```rust
+//! use mtgsdk::catalog::{CatalogCache, Validation};
+//! use mtgsdk::cards;
+//! use std::time::Duration;
+//! async {
+//!     let cache = CatalogCache::new(Duration::from_secs(24 * 60 * 60));
+//!     let catalog = cache.get().await.unwrap();
+//!     let filter = cards::filter().subtypes("goblinn").validate(&catalog, Validation::Reject);
+//!     assert!(filter.is_err());
+//! };
+//! ```
+#![allow(dead_code)]
+use crate::client::Client;
+use crate::formats;
+use crate::search::similarity;
+use crate::type_line::TypeCatalogs;
+use futures::lock::Mutex;
+use http::StatusCode;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+// A misspelled value is suggested the closest known value from this similarity on
+const SUGGESTION_SCORE: f64 = 0.6;
+
+/// Which list of the catalog.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CatalogKind {
+    Format,
+    Type,
+    Subtype,
+    Supertype,
+}
+
+impl CatalogKind {
+    /// The kind checked for a `cards::filter()` parameter (e.g.: "subtypes"), if any.
+    pub(crate) fn of_filter(key: &str) -> Option<CatalogKind> {
+        match key {
+            "gameFormat" => Some(CatalogKind::Format),
+            "types" => Some(CatalogKind::Type),
+            "subtypes" => Some(CatalogKind::Subtype),
+            "supertypes" => Some(CatalogKind::Supertype),
+            _ => None,
+        }
+    }
+}
+
+/// What `cards::Where::validate()` does with unknown values.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Validation {
+    /// Returns them as an error.
+    Reject,
+    /// Keeps the filter, and returns them alongside it.
+    Warn,
+}
+
+/// A filter value missing from the catalog.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownValue {
+    /// The `cards::filter()` parameter (e.g.: "subtypes").
+    pub filter: String,
+    pub value: String,
+    /// The closest known value, if any is close enough.
+    pub suggestion: Option<String>,
+}
+
+impl fmt::Display for UnknownValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unknown {} {:?}", self.filter, self.value)?;
+        if let Some(suggestion) = &self.suggestion {
+            write!(f, " (did you mean {:?}?)", suggestion)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for UnknownValue {}
+
+#[derive(Clone, Debug, Default)]
+struct Values {
+    values: HashSet<String>,
+    // Lowercase value to its canonical form
+    canonical: HashMap<String, String>,
+}
+
+impl Values {
+    fn new(values: HashSet<String>) -> Self {
+        let canonical = values
+            .iter()
+            .map(|value| (value.to_lowercase(), value.clone()))
+            .collect();
+        Values { values, canonical }
+    }
+}
+
+/// Formats, types, subtypes and supertypes known by the API.
+#[derive(Clone, Debug)]
+pub struct Catalog {
+    formats: Values,
+    types: Values,
+    subtypes: Values,
+    supertypes: Values,
+    fetched_at: Instant,
+}
+
+impl Catalog {
+    /// Builds a catalog from already known values (e.g.: saved from a previous run).
+    pub fn new(
+        formats: HashSet<String>,
+        types: HashSet<String>,
+        subtypes: HashSet<String>,
+        supertypes: HashSet<String>,
+    ) -> Self {
+        Catalog {
+            formats: Values::new(formats),
+            types: Values::new(types),
+            subtypes: Values::new(subtypes),
+            supertypes: Values::new(supertypes),
+            fetched_at: Instant::now(),
+        }
+    }
+
+    /// Function to get the four lists, at the same time.
+    ///
+    /// # Errors
+    /// If any call fails, it will return a `Err(StatusCode)`.
+    pub async fn fetch() -> Result<Catalog, StatusCode> {
+        Catalog::fetch_with(&Client::default()).await
+    }
+
+    /// Function to get the four lists, at the same time, with the given `Client`.
+    ///
+    /// # Errors
+    /// If any call fails, it will return a `Err(StatusCode)`.
+    pub async fn fetch_with(client: &Client) -> Result<Catalog, StatusCode> {
+        let (formats, types) =
+            futures::try_join!(formats::all_with(client), TypeCatalogs::fetch_with(client))?;
+        Ok(Catalog::new(
+            formats,
+            types.types,
+            types.subtypes,
+            types.supertypes,
+        ))
+    }
+
+    fn values(&self, kind: CatalogKind) -> &Values {
+        match kind {
+            CatalogKind::Format => &self.formats,
+            CatalogKind::Type => &self.types,
+            CatalogKind::Subtype => &self.subtypes,
+            CatalogKind::Supertype => &self.supertypes,
+        }
+    }
+
+    /// Every value of a list.
+    pub fn get(&self, kind: CatalogKind) -> &HashSet<String> {
+        &self.values(kind).values
+    }
+
+    pub fn contains(&self, kind: CatalogKind, value: &str) -> bool {
+        self.canonical(kind, value).is_some()
+    }
+
+    /// The value as the API writes it, ignoring case (e.g.: "legendary" is "Legendary").
+    pub fn canonical(&self, kind: CatalogKind, value: &str) -> Option<&str> {
+        self.values(kind)
+            .canonical
+            .get(&value.trim().to_lowercase())
+            .map(String::as_str)
+    }
+
+    /// The closest known value, if it is close enough (e.g.: "Goblinn" is "Goblin").
+    pub fn suggest(&self, kind: CatalogKind, value: &str) -> Option<&str> {
+        let value = value.trim().to_lowercase();
+        self.values(kind)
+            .canonical
+            .iter()
+            .map(|(lower, canonical)| (similarity(&value, lower), canonical))
+            .filter(|(score, _)| *score >= SUGGESTION_SCORE)
+            .max_by(|a, b| {
+                a.0.partial_cmp(&b.0)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+                    // The same score: the first in alphabetical order
+                    .then_with(|| b.1.cmp(a.1))
+            })
+            .map(|(_, canonical)| canonical.as_str())
+    }
+
+    /// How long ago the catalog was fetched (or built).
+    pub fn age(&self) -> Duration {
+        self.fetched_at.elapsed()
+    }
+
+    /// The types, subtypes and supertypes, to check type lines (see `type_line`).
+    pub fn type_catalogs(&self) -> TypeCatalogs {
+        TypeCatalogs {
+            types: self.types.values.clone(),
+            subtypes: self.subtypes.values.clone(),
+            supertypes: self.supertypes.values.clone(),
+        }
+    }
+
+    // Checks a filter value, which may hold many values joined by `,` (and) or `|` (or)
+    pub(crate) fn check(&self, filter: &str, value: &str) -> (String, Vec<UnknownValue>) {
+        let kind = match CatalogKind::of_filter(filter) {
+            Some(kind) => kind,
+            None => return (value.to_string(), Vec::new()),
+        };
+
+        let mut canonical = String::with_capacity(value.len());
+        let mut unknown = Vec::new();
+        let mut rest = value;
+        loop {
+            let end = rest.find([',', '|']).unwrap_or(rest.len());
+            let part = &rest[..end];
+            match self.canonical(kind, part) {
+                Some(known) => canonical.push_str(known),
+                None => {
+                    canonical.push_str(part);
+                    unknown.push(UnknownValue {
+                        filter: filter.to_string(),
+                        value: part.trim().to_string(),
+                        suggestion: self.suggest(kind, part).map(String::from),
+                    });
+                }
+            }
+            if end == rest.len() {
+                break;
+            }
+            canonical.push_str(&rest[end..end + 1]);
+            rest = &rest[end + 1..];
+        }
+        (canonical, unknown)
+    }
+}
+
+/// A `Catalog` fetched on first use, and again once older than its TTL.
+///
+/// Clones share the same catalog.
+#[derive(Clone, Debug)]
+pub struct CatalogCache {
+    client: Client,
+    ttl: Duration,
+    catalog: Arc<Mutex<Option<Arc<Catalog>>>>,
+}
+
+impl CatalogCache {
+    pub fn new(ttl: Duration) -> Self {
+        CatalogCache::with_client(&Client::default(), ttl)
+    }
+
+    /// Fetches with the given `Client` (see `client`).
+    pub fn with_client(client: &Client, ttl: Duration) -> Self {
+        CatalogCache {
+            client: client.clone(),
+            ttl,
+            catalog: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Function to get the catalog, fetching it if there is none yet or it is older than the TTL.
+    ///
+    /// Calls made while it is being fetched wait for it, instead of fetching it again.
+    ///
+    /// # Errors
+    /// If the catalog has to be fetched and the call fails, it will return a `Err(StatusCode)`.
+    /// The catalog held, if any, is kept.
+    pub async fn get(&self) -> Result<Arc<Catalog>, StatusCode> {
+        let mut cached = self.catalog.lock().await;
+        if let Some(catalog) = cached.as_ref().filter(|c| c.age() < self.ttl) {
+            return Ok(catalog.clone());
+        }
+        let catalog = Arc::new(Catalog::fetch_with(&self.client).await?);
+        *cached = Some(catalog.clone());
+        Ok(catalog)
+    }
+
+    /// Drops the catalog held, so that the next `get()` fetches it.
+    pub async fn invalidate(&self) {
+        *self.catalog.lock().await = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cards;
+    use crate::catalog::{CatalogCache, CatalogKind, Validation};
+    use crate::client::tests::Fake;
+    use crate::client::Client;
+    use futures::executor::block_on;
+    use http::StatusCode;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    fn fake() -> Fake {
+        Fake::new(&[
+            ("/v1/formats", r#"{"formats": ["Modern", "Legacy"]}"#),
+            ("/v1/types", r#"{"types": ["Creature", "Artifact"]}"#),
+            (
+                "/v1/subtypes",
+                r#"{"subtypes": ["Goblin", "Human", "Time Lord"]}"#,
+            ),
+            (
+                "/v1/supertypes",
+                r#"{"supertypes": ["Legendary", "Basic"]}"#,
+            ),
+        ])
+    }
+
+    #[test]
+    fn canonical_values() {
+        let client = Client::with_transport(fake());
+        let cache = CatalogCache::with_client(&client, Duration::from_secs(60));
+        let catalog = block_on(cache.get()).unwrap();
+
+        assert_eq!(
+            catalog.canonical(CatalogKind::Supertype, "legendary"),
+            Some("Legendary")
+        );
+        assert_eq!(
+            catalog.canonical(CatalogKind::Subtype, "TIME LORD"),
+            Some("Time Lord")
+        );
+        assert!(!catalog.contains(CatalogKind::Format, "Pauper"));
+        assert_eq!(
+            catalog.suggest(CatalogKind::Subtype, "goblinn"),
+            Some("Goblin")
+        );
+        assert_eq!(catalog.suggest(CatalogKind::Subtype, "zzz"), None);
+        assert!(Arc::ptr_eq(&catalog, &block_on(cache.get()).unwrap()));
+        assert_eq!(catalog.type_catalogs().types.len(), 2);
+    }
+
+    #[test]
+    fn cache_ttl() {
+        let client = Client::with_transport(fake());
+        let cache = CatalogCache::with_client(&client, Duration::ZERO);
+        let first = block_on(cache.get()).unwrap();
+        assert!(!Arc::ptr_eq(&first, &block_on(cache.get()).unwrap()));
+
+        let broken =
+            Client::with_transport(fake().status("/v1/types", StatusCode::SERVICE_UNAVAILABLE));
+        let cache = CatalogCache::with_client(&broken, Duration::from_secs(60));
+        assert_eq!(
+            block_on(cache.get()).unwrap_err(),
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+    }
+
+    #[test]
+    fn validate_filter() {
+        let client = Client::with_transport(fake());
+        let catalog =
+            block_on(CatalogCache::with_client(&client, Duration::from_secs(60)).get()).unwrap();
+
+        let (filter, unknown) = cards::filter()
+            .supertypes("legendary")
+            .subtypes("goblin|human")
+            .game_format("modern")
+            .validate(&catalog, Validation::Reject)
+            .unwrap();
+        assert!(unknown.is_empty());
+        assert_eq!(
+            filter.query_string(),
+            "?supertypes=Legendary&subtypes=Goblin|Human&gameFormat=Modern"
+        );
+
+        let unknown = cards::filter()
+            .types("creature,artifcat")
+            .validate(&catalog, Validation::Reject)
+            .unwrap_err();
+        assert_eq!(unknown.len(), 1);
+        assert_eq!(unknown[0].value, "artifcat");
+        assert_eq!(unknown[0].suggestion.as_deref(), Some("Artifact"));
+        assert_eq!(
+            unknown[0].to_string(),
+            "unknown types \"artifcat\" (did you mean \"Artifact\"?)"
+        );
+
+        let (filter, unknown) = cards::filter()
+            .types("artifcat")
+            .validate(&catalog, Validation::Warn)
+            .unwrap();
+        assert_eq!(filter.query_string(), "?types=artifcat");
+        assert_eq!(unknown[0].suggestion.as_deref(), Some("Artifact"));
+    }
+}
@@ -0,0 +1,470 @@
+//! HTTP client used by the API calls, with a pluggable transport.
+//!
+//! Every call goes through a `Client`, which hands the HTTP requests to an `HttpTransport`.
+//! The calls without a client (e.g.: `cards::find()`) use `Client::default()`; the ones ending in `_with`
+//! (e.g.: `cards::find_with()`) and the filters' `client()` take the client to use.
+//!
+//! With the `reqwest` feature (enabled by default), the default transport is `ReqwestTransport`.
+//! To use another HTTP stack, an in-memory fake in tests or another async runtime, implement `HttpTransport`:
+//! ```rust
+//! use mtgsdk::client::{Client, HttpTransport, TransportFuture};
+//! use mtgsdk::cards;
+//!
+//! struct Fake;
+//!
+//! impl HttpTransport for Fake {
+//!     fn send(&self, request: http::Request<()>) -> TransportFuture<'_> {
+//!         let body = r#"{"card": {"name": "Narset, Enlightened Master", "layout": "normal", "cmc": 6.0,
+//!             "type": "Legendary Creature — Human Monk", "types": ["Creature"], "rarity": "Mythic Rare",
+//!             "set": "KTK", "setName": "Khans of Tarkir", "artist": "Magali Villeneuve", "number": "190",
+//!             "id": "a0dc3a09", "printings": ["KTK"]}}"#;
+//!         Box::pin(async move {
+//!             assert!(request.uri().path().ends_with("/cards/386616"));
+//!             Ok(http::Response::new(body.as_bytes().to_vec()))
+//!         })
+//!     }
+//! }
+//!
+//! let client = Client::with_transport(Fake);
+//! let card = futures::executor::block_on(cards::find_with(&client, 386616));
+//! assert_eq!(card.unwrap().name, "Narset, Enlightened Master");
+//! ```
+use crate::query_builder::{API_URL, API_VER};
+use http::{HeaderMap, StatusCode};
+use serde::de::DeserializeOwned;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Future returned by `HttpTransport::send()`.
+pub type TransportFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<http::Response<Vec<u8>>, StatusCode>> + Send + 'a>>;
+
+/// Future returned by `HttpTransport::sleep()`.
+pub type SleepFuture<'a> = Pin<Box<dyn Future<Output = ()> + Send + 'a>>;
+
+/// Sends the HTTP requests of a `Client`.
+///
+/// The API calls are all `GET` requests without body. The transport returns the response as it is
+/// (status, headers and body); errors statuses are handled by the `Client`.
+/// If no response could be obtained at all, return an error status (e.g.: `StatusCode::BAD_GATEWAY`).
+///
+/// `sleep()` waits before retrying a request refused by the rate limit. By default it waits on a separate thread,
+/// so it works with any runtime; override it to use the runtime's timer.
+pub trait HttpTransport: Send + Sync {
+    fn send(&self, request: http::Request<()>) -> TransportFuture<'_>;
+
+    fn sleep(&self, duration: Duration) -> SleepFuture<'_> {
+        let (done, wait) = futures::channel::oneshot::channel();
+        std::thread::spawn(move || {
+            std::thread::sleep(duration);
+            let _ = done.send(());
+        });
+        Box::pin(async move {
+            let _ = wait.await;
+        })
+    }
+}
+
+// Waits before retrying a request refused with `429 Too Many Requests`, when the response says nothing
+const FIRST_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Default transport, based on `reqwest`.
+///
+/// As `reqwest`, it must run within a [tokio](https://tokio.rs/) runtime.
+#[cfg(feature = "reqwest")]
+#[derive(Clone, Debug, Default)]
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+}
+
+#[cfg(feature = "reqwest")]
+impl ReqwestTransport {
+    /// Uses an already configured `reqwest::Client` (e.g.: with a proxy or a timeout).
+    pub fn new(client: reqwest::Client) -> Self {
+        ReqwestTransport { client }
+    }
+}
+
+#[cfg(feature = "reqwest")]
+impl HttpTransport for ReqwestTransport {
+    fn send(&self, request: http::Request<()>) -> TransportFuture<'_> {
+        Box::pin(async move {
+            let response = self
+                .client
+                .get(request.uri().to_string())
+                .headers(request.headers().clone())
+                .send()
+                .await;
+
+            let response = match response {
+                Ok(r) => r,
+                Err(e) => match e.status() {
+                    Some(status) => return Err(status),
+                    None => return Err(StatusCode::BAD_REQUEST),
+                },
+            };
+
+            let status = response.status();
+            let headers = response.headers().clone();
+            let body = match response.bytes().await {
+                Ok(b) => b.to_vec(),
+                Err(_) => return Err(StatusCode::BAD_REQUEST),
+            };
+
+            let mut converted = http::Response::new(body);
+            *converted.status_mut() = status;
+            *converted.headers_mut() = headers;
+            Ok(converted)
+        })
+    }
+}
+
+/// Transport of the blocking API (see `blocking`), based on `reqwest::blocking`.
+///
+/// The request is sent when its future is first polled, blocking the thread until the response:
+/// it must not be used from inside an async runtime.
+#[cfg(feature = "blocking")]
+#[derive(Clone, Debug, Default)]
+pub struct BlockingTransport {
+    client: reqwest::blocking::Client,
+}
+
+#[cfg(feature = "blocking")]
+impl BlockingTransport {
+    /// Uses an already configured `reqwest::blocking::Client` (e.g.: with a proxy or a timeout).
+    pub fn new(client: reqwest::blocking::Client) -> Self {
+        BlockingTransport { client }
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl HttpTransport for BlockingTransport {
+    fn send(&self, request: http::Request<()>) -> TransportFuture<'_> {
+        Box::pin(async move {
+            let response = self
+                .client
+                .get(request.uri().to_string())
+                .headers(request.headers().clone())
+                .send();
+
+            let response = match response {
+                Ok(r) => r,
+                Err(e) => match e.status() {
+                    Some(status) => return Err(status),
+                    None => return Err(StatusCode::BAD_REQUEST),
+                },
+            };
+
+            let status = response.status();
+            let headers = response.headers().clone();
+            let body = match response.bytes() {
+                Ok(b) => b.to_vec(),
+                Err(_) => return Err(StatusCode::BAD_REQUEST),
+            };
+
+            let mut converted = http::Response::new(body);
+            *converted.status_mut() = status;
+            *converted.headers_mut() = headers;
+            Ok(converted)
+        })
+    }
+
+    // Blocks the thread, as the requests do
+    fn sleep(&self, duration: Duration) -> SleepFuture<'_> {
+        std::thread::sleep(duration);
+        Box::pin(async {})
+    }
+}
+
+// Stands for the default transport when the `reqwest` feature is disabled
+#[cfg(not(feature = "reqwest"))]
+struct NoTransport;
+
+#[cfg(not(feature = "reqwest"))]
+impl HttpTransport for NoTransport {
+    fn send(&self, _request: http::Request<()>) -> TransportFuture<'_> {
+        Box::pin(async { Err(StatusCode::NOT_IMPLEMENTED) })
+    }
+}
+
+/// Client for the API calls.
+///
+/// The calls fail with the status of the response when it is not `200 OK`, and with
+/// `StatusCode::UNPROCESSABLE_ENTITY` when its body is not the expected Json.
+///
+/// Cloning a client is cheap: the clones share the same transport.
+#[derive(Clone)]
+pub struct Client {
+    transport: Arc<dyn HttpTransport>,
+    base_url: String,
+}
+
+impl Client {
+    /// Creates a client with the default transport (see `Client::default()`).
+    pub fn new() -> Self {
+        Client::default()
+    }
+
+    /// Creates a client sending its requests through `transport`.
+    pub fn with_transport<T>(transport: T) -> Self
+    where
+        T: HttpTransport + 'static,
+    {
+        Client {
+            transport: Arc::new(transport),
+            base_url: format!("{}/{}", API_URL, API_VER),
+        }
+    }
+
+    /// Changes the API address (by default, `https://api.magicthegathering.io/v1`), e.g.: to use a mirror.
+    pub fn base_url(mut self, url: &str) -> Self {
+        self.base_url = url.trim_end_matches('/').to_string();
+        self
+    }
+
+    // Make the call and parse the response body as Json, keeping the response headers
+    pub(crate) async fn get<T>(&self, path: &str) -> Result<(T, HeaderMap), StatusCode>
+    where
+        T: DeserializeOwned,
+    {
+        parse(self.send(path).await?)
+    }
+
+    // As `get()`, but the requests refused with `429 Too Many Requests` are retried up to `retries` times, after
+    // the delay of their `Retry-After` (or `Ratelimit-Reset`) header, or else an exponential backoff
+    pub(crate) async fn get_retrying<T>(
+        &self,
+        path: &str,
+        retries: u32,
+    ) -> Result<(T, HeaderMap), StatusCode>
+    where
+        T: DeserializeOwned,
+    {
+        let mut backoff = FIRST_BACKOFF;
+        for _ in 0..retries {
+            let response = self.send(path).await?;
+            if response.status() != StatusCode::TOO_MANY_REQUESTS {
+                return parse(response);
+            }
+            let delay = retry_after(response.headers()).unwrap_or(backoff);
+            self.transport.sleep(delay.min(MAX_BACKOFF)).await;
+            backoff = (backoff * 2).min(MAX_BACKOFF);
+        }
+        self.get(path).await
+    }
+
+    async fn send(&self, path: &str) -> Result<http::Response<Vec<u8>>, StatusCode> {
+        // The query string is already encoded, value by value (see `query_builder::query_string()`)
+        let url = match path.split_once('?') {
+            Some((path, query)) => format!("{}/{}?{}", self.base_url, encode_path(path), query),
+            None => format!("{}/{}", self.base_url, encode_path(path)),
+        };
+        let request = match http::Request::get(url).body(()) {
+            Ok(r) => r,
+            Err(_) => return Err(StatusCode::BAD_REQUEST),
+        };
+        self.transport.send(request).await
+    }
+}
+
+// Parse the body of a successful response as Json (a body that does not fit `T` is `UNPROCESSABLE_ENTITY`)
+fn parse<T>(response: http::Response<Vec<u8>>) -> Result<(T, HeaderMap), StatusCode>
+where
+    T: DeserializeOwned,
+{
+    if response.status() != StatusCode::OK {
+        return Err(response.status());
+    }
+
+    let (parts, body) = response.into_parts();
+    match serde_json::from_slice::<T>(&body) {
+        Ok(s) => Ok((s, parts.headers)),
+        Err(_) => Err(StatusCode::UNPROCESSABLE_ENTITY),
+    }
+}
+
+// Delay asked by a `429 Too Many Requests` response, in seconds
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    ["retry-after", "ratelimit-reset"].iter().find_map(|name| {
+        let seconds: u64 = headers.get(*name)?.to_str().ok()?.trim().parse().ok()?;
+        Some(Duration::from_secs(seconds))
+    })
+}
+
+// Percent-encode what a URI path cannot hold as is (e.g.: spaces, quotes and accents in card names)
+fn encode_path(path: &str) -> String {
+    let mut encoded = String::with_capacity(path.len());
+    for byte in path.bytes() {
+        if byte <= b' ' || byte >= 0x7F || b"\"#%<>?\\^`".contains(&byte) {
+            encoded.push_str(&format!("%{:02X}", byte));
+        } else {
+            encoded.push(byte as char);
+        }
+    }
+    encoded
+}
+
+/// Without the `reqwest` feature there is no default transport, and calls made with the default client
+/// fail with `StatusCode::NOT_IMPLEMENTED`.
+impl Default for Client {
+    #[cfg(feature = "reqwest")]
+    fn default() -> Self {
+        Client::with_transport(ReqwestTransport::default())
+    }
+
+    #[cfg(not(feature = "reqwest"))]
+    fn default() -> Self {
+        Client::with_transport(NoTransport)
+    }
+}
+
+impl fmt::Debug for Client {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Client")
+            .field("base_url", &self.base_url)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::client::{Client, HttpTransport, SleepFuture, TransportFuture};
+    use crate::{formats, sets};
+    use futures::executor::block_on;
+    use http::StatusCode;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    /// In-memory transport answering each path (with its query) with a canned body.
+    pub(crate) struct Fake {
+        pub responses: Vec<(String, StatusCode, String)>,
+        pub headers: Vec<(&'static str, String)>,
+        pub requests: Mutex<Vec<String>>,
+        pub once: Mutex<Vec<(String, StatusCode)>>,
+        pub sleeps: Mutex<Vec<Duration>>,
+    }
+
+    impl Fake {
+        pub(crate) fn new(responses: &[(&str, &str)]) -> Self {
+            Fake {
+                responses: responses
+                    .iter()
+                    .map(|(path, body)| (path.to_string(), StatusCode::OK, body.to_string()))
+                    .collect(),
+                headers: Vec::new(),
+                requests: Mutex::new(Vec::new()),
+                once: Mutex::new(Vec::new()),
+                sleeps: Mutex::new(Vec::new()),
+            }
+        }
+
+        // Answer `path` with an error status
+        pub(crate) fn status(mut self, path: &str, status: StatusCode) -> Self {
+            self.responses
+                .insert(0, (path.to_string(), status, String::new()));
+            self
+        }
+
+        // Answer `path` with an error status the first time only
+        pub(crate) fn status_once(self, path: &str, status: StatusCode) -> Self {
+            self.once.lock().unwrap().push((path.to_string(), status));
+            self
+        }
+
+        // Add a header to every response
+        pub(crate) fn header(mut self, name: &'static str, value: &str) -> Self {
+            self.headers.push((name, value.to_string()));
+            self
+        }
+    }
+
+    // Shares the fake with the test, to look at the requests sent
+    impl HttpTransport for Arc<Fake> {
+        fn send(&self, request: http::Request<()>) -> TransportFuture<'_> {
+            self.as_ref().send(request)
+        }
+
+        fn sleep(&self, duration: Duration) -> SleepFuture<'_> {
+            self.as_ref().sleep(duration)
+        }
+    }
+
+    impl HttpTransport for Fake {
+        fn send(&self, request: http::Request<()>) -> TransportFuture<'_> {
+            let uri = request.uri();
+            let path = match uri.query() {
+                Some(q) => format!("{}?{}", uri.path(), q),
+                None => uri.path().to_string(),
+            };
+            self.requests.lock().unwrap().push(path.clone());
+
+            let mut once = self.once.lock().unwrap();
+            let found = match once.iter().position(|(p, _)| *p == path) {
+                Some(at) => Some((once.remove(at).1, "")),
+                None => self
+                    .responses
+                    .iter()
+                    .find(|(p, _, _)| *p == path)
+                    .map(|(_, status, body)| (*status, body.as_str())),
+            };
+            let mut response = match found {
+                Some((status, body)) => {
+                    let mut response = http::Response::new(body.as_bytes().to_vec());
+                    *response.status_mut() = status;
+                    response
+                }
+                None => {
+                    let mut response = http::Response::new(Vec::new());
+                    *response.status_mut() = StatusCode::NOT_FOUND;
+                    response
+                }
+            };
+            for (name, value) in self.headers.iter() {
+                response.headers_mut().insert(*name, value.parse().unwrap());
+            }
+            Box::pin(async move { Ok(response) })
+        }
+
+        // Records the delay instead of waiting
+        fn sleep(&self, duration: Duration) -> SleepFuture<'_> {
+            self.sleeps.lock().unwrap().push(duration);
+            Box::pin(async {})
+        }
+    }
+
+    #[test]
+    fn fake_transport() {
+        let client = Client::with_transport(Fake::new(&[(
+            "/v1/formats",
+            r#"{"formats": ["Modern", "Legacy"]}"#,
+        )]));
+        let formats = block_on(formats::all_with(&client));
+        assert!(formats.unwrap().contains("Modern"));
+    }
+
+    #[test]
+    fn fake_transport_not_found() {
+        let client = Client::with_transport(Fake::new(&[]));
+        let set = block_on(sets::find_with(&client, "xyz"));
+        assert_eq!(set.unwrap_err(), StatusCode::NOT_FOUND);
+    }
+
+    #[test]
+    fn fake_transport_invalid_body() {
+        let client = Client::with_transport(Fake::new(&[("/v1/formats", r#"{"sets": []}"#)]));
+        let formats = block_on(formats::all_with(&client));
+        assert_eq!(formats.unwrap_err(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[test]
+    fn fake_transport_filter() {
+        let fake = Fake::new(&[("/v1/sets/?name=mira", r#"{"sets": []}"#)]);
+        let client = Client::with_transport(fake).base_url("https://example.com/v1/");
+        let sets = block_on(sets::filter().client(&client).name("mira").all());
+        assert!(sets.unwrap().is_empty());
+    }
+}
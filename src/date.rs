@@ -0,0 +1,253 @@
+//! Calendar dates, as the API writes them (e.g.: "2015-06-01"), used by `Set::release_date` and `Card::release_date`.
+#![allow(dead_code)]
+use serde::de::{self, Deserializer};
+use serde::{Deserialize, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Error reading a `Date`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseDateError {
+    input: String,
+}
+
+impl fmt::Display for ParseDateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid date: {:?}", self.input)
+    }
+}
+
+impl std::error::Error for ParseDateError {}
+
+/// A day of the calendar, ordered chronologically.
+///
+/// # Example
+/// ```rust
+/// use mtgsdk::date::Date;
+///
+/// let date: Date = "2015-06-01".parse().unwrap();
+/// assert_eq!((date.year(), date.month(), date.day()), (2015, 6, 1));
+/// assert!(date < Date::new(2015, 6, 2).unwrap());
+/// assert!("2015-02-29".parse::<Date>().is_err());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Date {
+    year: i32,
+    month: u8,
+    day: u8,
+}
+
+impl Date {
+    /// The date, if it exists (e.g.: not February 30th).
+    pub fn new(year: i32, month: u32, day: u32) -> Option<Date> {
+        if !(1..=12).contains(&month) || day < 1 || day > days_in_month(year, month as u8) as u32 {
+            return None;
+        }
+        Some(Date {
+            year,
+            month: month as u8,
+            day: day as u8,
+        })
+    }
+
+    /// Reads "YYYY-MM-DD". Dates known only to the month ("YYYY-MM") or year ("YYYY") are taken as their first day.
+    ///
+    /// # Errors
+    /// If the text is not a date, it will return a `Err(ParseDateError)`.
+    pub fn parse(date: &str) -> Result<Date, ParseDateError> {
+        let error = || ParseDateError {
+            input: date.to_string(),
+        };
+        let mut parts = date.trim().split('-');
+        let year: i32 = parts
+            .next()
+            .filter(|y| y.len() == 4)
+            .and_then(|y| y.parse().ok())
+            .ok_or_else(error)?;
+        let mut next = || -> Result<u32, ParseDateError> {
+            match parts.next() {
+                None => Ok(1),
+                Some(p) if p.len() == 2 => p.parse().map_err(|_| error()),
+                Some(_) => Err(error()),
+            }
+        };
+        let month = next()?;
+        let day = next()?;
+        if parts.next().is_some() {
+            return Err(error());
+        }
+        Date::new(year, month, day).ok_or_else(error)
+    }
+
+    /// Today, in UTC.
+    pub fn today() -> Date {
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        Date::from_days((seconds / 86_400) as i64)
+    }
+
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    pub fn month(&self) -> u32 {
+        self.month as u32
+    }
+
+    pub fn day(&self) -> u32 {
+        self.day as u32
+    }
+
+    /// Days since 1970-01-01 (negative before).
+    pub fn days(&self) -> i64 {
+        // Days from civil, see http://howardhinnant.github.io/date_algorithms.html
+        let year = self.year as i64 - if self.month <= 2 { 1 } else { 0 };
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let month = self.month as i64;
+        let day_of_year =
+            (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + self.day as i64 - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    /// The date `days` after 1970-01-01.
+    pub fn from_days(days: i64) -> Date {
+        let days = days + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days - era * 146_097;
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let mp = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u8;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u8;
+        let year = (year_of_era + era * 400 + if month <= 2 { 1 } else { 0 }) as i32;
+        Date { year, month, day }
+    }
+
+    /// The date `days` later (or earlier, if negative).
+    pub fn add_days(&self, days: i64) -> Date {
+        Date::from_days(self.days() + days)
+    }
+
+    /// The same day `years` later (or earlier); February 29th becomes February 28th out of leap years.
+    pub fn add_years(&self, years: i32) -> Date {
+        let year = self.year + years;
+        let day = self.day.min(days_in_month(year, self.month));
+        Date {
+            year,
+            month: self.month,
+            day,
+        }
+    }
+}
+
+fn is_leap(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+fn days_in_month(year: i32, month: u8) -> u8 {
+    match month {
+        2 if is_leap(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+impl Default for Date {
+    /// 1970-01-01.
+    fn default() -> Self {
+        Date::from_days(0)
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl FromStr for Date {
+    type Err = ParseDateError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Date::parse(s)
+    }
+}
+
+impl Serialize for Date {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Date {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        Date::parse(&text).map_err(de::Error::custom)
+    }
+}
+
+// Optional date of the API's data (`#[serde(default, deserialize_with = "date::lenient")]`): a missing,
+// empty or malformed one is `None` instead of failing the whole response
+pub(crate) fn lenient<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<Date>, D::Error> {
+    let value = Option::<serde_json::Value>::deserialize(deserializer)?;
+    Ok(value
+        .as_ref()
+        .and_then(|value| value.as_str())
+        .and_then(|text| Date::parse(text).ok()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::date::Date;
+
+    #[test]
+    fn parse_dates() {
+        assert_eq!(Date::parse("1993-08-05").unwrap().to_string(), "1993-08-05");
+        assert_eq!(Date::parse("2000-02").unwrap().to_string(), "2000-02-01");
+        assert_eq!(Date::parse("1994").unwrap().to_string(), "1994-01-01");
+        assert!(Date::parse("2000-02-29").is_ok());
+        assert!(Date::parse("1900-02-29").is_err());
+        assert!(Date::parse("2015-6-1").is_err());
+        assert!(Date::parse("2015-06-01-01").is_err());
+        assert!(Date::parse("").is_err());
+
+        let date: Date = serde_json::from_str(r#""2015-06-01""#).unwrap();
+        assert_eq!(serde_json::to_string(&date).unwrap(), r#""2015-06-01""#);
+        assert!(serde_json::from_str::<Date>(r#""June 2015""#).is_err());
+    }
+
+    #[test]
+    fn lenient_dates() {
+        #[derive(serde::Deserialize)]
+        struct Dated {
+            #[serde(default, deserialize_with = "crate::date::lenient")]
+            date: Option<Date>,
+        }
+        let date = |json: &str| serde_json::from_str::<Dated>(json).unwrap().date;
+        assert_eq!(date(r#"{"date": "2015-06-01"}"#), Date::new(2015, 6, 1));
+        assert_eq!(date(r#"{"date": "June 2015"}"#), None);
+        assert_eq!(date(r#"{"date": 2015}"#), None);
+        assert_eq!(date(r#"{"date": null}"#), None);
+        assert_eq!(date("{}"), None);
+    }
+
+    #[test]
+    fn days_arithmetic() {
+        assert_eq!(Date::default().to_string(), "1970-01-01");
+        let date = Date::new(2016, 2, 29).unwrap();
+        assert_eq!(Date::from_days(date.days()), date);
+        assert_eq!(date.add_days(1).to_string(), "2016-03-01");
+        assert_eq!(date.add_days(-60).to_string(), "2015-12-31");
+        assert_eq!(date.add_years(1).to_string(), "2017-02-28");
+        assert_eq!(Date::new(1969, 12, 31).unwrap().days(), -1);
+        assert!(Date::today() > date);
+    }
+}
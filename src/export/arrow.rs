@@ -0,0 +1,369 @@
+//! Convert cards into [Apache Arrow](https://arrow.apache.org/) record batches and write them as Parquet files.
+//!
+//! Requires the `arrow` feature.
+//!
+//! Unlike `export::csv`, nothing is flattened: multi-valued fields become list columns (e.g.: `colors`, `types`)
+//! and nested structures become lists of structs (`rulings`, `foreignNames`, `legalities`).
+//! Columns are named as in the API's JSON.
+#![allow(dead_code)]
+use crate::cards::Card;
+use arrow_array::builder::{
+    Float64Builder, ListBuilder, StringBuilder, StructBuilder, UInt64Builder,
+};
+use arrow_array::{ArrayRef, RecordBatch};
+use arrow_schema::{ArrowError, DataType, Field, Fields, Schema, SchemaRef};
+use parquet::arrow::ArrowWriter;
+use parquet::errors::ParquetError;
+use parquet::file::properties::WriterProperties;
+use std::collections::HashSet;
+use std::io::Write;
+use std::sync::Arc;
+
+// Rows per record batch when writing Parquet files
+const BATCH_SIZE: usize = 8192;
+
+fn string_fields(names: &[&str]) -> Fields {
+    names
+        .iter()
+        .map(|name| Field::new(*name, DataType::Utf8, true))
+        .collect()
+}
+
+fn rulings_fields() -> Fields {
+    string_fields(&["date", "text"])
+}
+
+fn legalities_fields() -> Fields {
+    string_fields(&["format", "legality"])
+}
+
+fn foreign_names_fields() -> Fields {
+    let mut fields: Vec<Field> =
+        string_fields(&["name", "text", "type", "flavor", "imageUrl", "language"])
+            .iter()
+            .map(|f| f.as_ref().clone())
+            .collect();
+    fields.push(Field::new("multiverseid", DataType::UInt64, true));
+    fields.into()
+}
+
+// Sorted, so the same card always gives the same list
+fn append_set<T: AsRef<str>>(
+    builder: &mut ListBuilder<StringBuilder>,
+    values: Option<&HashSet<T>>,
+) {
+    match values {
+        Some(values) => {
+            let mut values: Vec<&str> = values.iter().map(AsRef::as_ref).collect();
+            values.sort_unstable();
+            for value in values {
+                builder.values().append_value(value);
+            }
+            builder.append(true);
+        }
+        None => builder.append(false),
+    }
+}
+
+fn string_field(builder: &mut StructBuilder, i: usize) -> &mut StringBuilder {
+    builder
+        .field_builder::<StringBuilder>(i)
+        .expect("struct fields are strings")
+}
+
+/// Function to convert cards into a single Arrow record batch, one row per card.
+///
+/// # Example
+/// ```rust
+/// use mtgsdk::cards::Card;
+/// use mtgsdk::export::arrow;
+///
+/// let cards = vec![Card { name: "Karn Liberated".to_string(), cmc: 7.0, ..Default::default() }];
+/// let batch = arrow::to_record_batch(&cards).unwrap();
+/// assert_eq!(batch.num_rows(), 1);
+/// assert!(batch.column_by_name("legalities").is_some());
+/// ```
+///
+/// # Errors
+/// If the batch cannot be built, it will return a `Err(ArrowError)`.
+pub fn to_record_batch(cards: &[Card]) -> Result<RecordBatch, ArrowError> {
+    let capacity = cards.len();
+    let mut name = StringBuilder::new();
+    let mut layout = StringBuilder::new();
+    let mut cmc = Float64Builder::with_capacity(capacity);
+    let mut colors = ListBuilder::new(StringBuilder::new());
+    let mut color_identity = ListBuilder::new(StringBuilder::new());
+    let mut type_field = StringBuilder::new();
+    let mut supertypes = ListBuilder::new(StringBuilder::new());
+    let mut types = ListBuilder::new(StringBuilder::new());
+    let mut subtypes = ListBuilder::new(StringBuilder::new());
+    let mut rarity = StringBuilder::new();
+    let mut set_field = StringBuilder::new();
+    let mut set_name = StringBuilder::new();
+    let mut text = StringBuilder::new();
+    let mut artist = StringBuilder::new();
+    let mut number = StringBuilder::new();
+    let mut power = StringBuilder::new();
+    let mut toughness = StringBuilder::new();
+    let mut loyalty = StringBuilder::new();
+    let mut id = StringBuilder::new();
+    let mut multiverseid = UInt64Builder::new();
+    let mut names = ListBuilder::new(StringBuilder::new());
+    let mut mana_cost = StringBuilder::new();
+    let mut variations = ListBuilder::new(StringBuilder::new());
+    let mut image_url = StringBuilder::new();
+    let mut watermark = StringBuilder::new();
+    let mut border = StringBuilder::new();
+    let mut release_date = StringBuilder::new();
+    let mut rulings = ListBuilder::new(StructBuilder::from_fields(rulings_fields(), 0));
+    let mut foreign_names = ListBuilder::new(StructBuilder::from_fields(foreign_names_fields(), 0));
+    let mut printings = ListBuilder::new(StringBuilder::new());
+    let mut original_text = StringBuilder::new();
+    let mut original_type = StringBuilder::new();
+    let mut legalities = ListBuilder::new(StructBuilder::from_fields(legalities_fields(), 0));
+
+    for card in cards {
+        name.append_value(&card.name);
+        layout.append_value(&card.layout);
+        cmc.append_value(card.cmc);
+        append_set(&mut colors, card.colors.as_ref());
+        append_set(&mut color_identity, card.color_identity.as_ref());
+        type_field.append_value(&card.type_field);
+        append_set(&mut supertypes, card.supertypes.as_ref());
+        append_set(&mut types, Some(&card.types));
+        append_set(&mut subtypes, card.subtypes.as_ref());
+        rarity.append_value(&card.rarity);
+        set_field.append_value(&card.set_field);
+        set_name.append_value(&card.set_name);
+        text.append_value(&card.text);
+        artist.append_value(&card.artist);
+        number.append_value(&card.number);
+        power.append_option(card.power.as_ref());
+        toughness.append_option(card.toughness.as_ref());
+        loyalty.append_option(card.loyalty.as_ref());
+        id.append_value(&card.id);
+        multiverseid.append_option(card.multiverseid.map(|id| id.0));
+        append_set(&mut names, card.names.as_ref());
+        mana_cost.append_option(card.mana_cost.as_ref());
+        match &card.variations {
+            Some(v) => {
+                for variation in v {
+                    variations.values().append_value(variation);
+                }
+                variations.append(true);
+            }
+            None => variations.append(false),
+        }
+        image_url.append_option(card.image_url.as_ref());
+        watermark.append_option(card.watermark.as_ref());
+        border.append_option(card.border.as_ref());
+        release_date.append_option(card.release_date.map(|date| date.to_string()));
+
+        match &card.rulings {
+            Some(r) => {
+                for ruling in r {
+                    let item = rulings.values();
+                    string_field(item, 0).append_value(&ruling.date);
+                    string_field(item, 1).append_value(&ruling.text);
+                    item.append(true);
+                }
+                rulings.append(true);
+            }
+            None => rulings.append(false),
+        }
+
+        for foreign in &card.foreign_names {
+            let item = foreign_names.values();
+            string_field(item, 0).append_value(&foreign.name);
+            string_field(item, 1).append_value(&foreign.text);
+            string_field(item, 2).append_option(foreign.type_field.as_ref());
+            string_field(item, 3).append_option(foreign.flavor.as_ref());
+            string_field(item, 4).append_option(foreign.image_url.as_ref());
+            string_field(item, 5).append_value(&foreign.language);
+            item.field_builder::<UInt64Builder>(6)
+                .expect("multiverseid is an integer")
+                .append_option(foreign.multiverseid.map(|id| id.0));
+            item.append(true);
+        }
+        foreign_names.append(true);
+
+        append_set(&mut printings, Some(&card.printings));
+        original_text.append_option(card.original_text.as_ref());
+        original_type.append_option(card.original_type.as_ref());
+
+        for legality in &card.legalities {
+            let item = legalities.values();
+            string_field(item, 0).append_value(&legality.format);
+            string_field(item, 1).append_value(&legality.legality);
+            item.append(true);
+        }
+        legalities.append(true);
+    }
+
+    // Optional fields of `Card` are the only nullable columns
+    let columns: Vec<(&str, bool, ArrayRef)> = vec![
+        ("name", false, Arc::new(name.finish())),
+        ("layout", false, Arc::new(layout.finish())),
+        ("cmc", false, Arc::new(cmc.finish())),
+        ("colors", true, Arc::new(colors.finish())),
+        ("colorIdentity", true, Arc::new(color_identity.finish())),
+        ("type", false, Arc::new(type_field.finish())),
+        ("supertypes", true, Arc::new(supertypes.finish())),
+        ("types", false, Arc::new(types.finish())),
+        ("subtypes", true, Arc::new(subtypes.finish())),
+        ("rarity", false, Arc::new(rarity.finish())),
+        ("set", false, Arc::new(set_field.finish())),
+        ("setName", false, Arc::new(set_name.finish())),
+        ("text", false, Arc::new(text.finish())),
+        ("artist", false, Arc::new(artist.finish())),
+        ("number", false, Arc::new(number.finish())),
+        ("power", true, Arc::new(power.finish())),
+        ("toughness", true, Arc::new(toughness.finish())),
+        ("loyalty", true, Arc::new(loyalty.finish())),
+        ("id", false, Arc::new(id.finish())),
+        ("multiverseid", true, Arc::new(multiverseid.finish())),
+        ("names", true, Arc::new(names.finish())),
+        ("manaCost", true, Arc::new(mana_cost.finish())),
+        ("variations", true, Arc::new(variations.finish())),
+        ("imageUrl", true, Arc::new(image_url.finish())),
+        ("watermark", true, Arc::new(watermark.finish())),
+        ("border", true, Arc::new(border.finish())),
+        ("releaseDate", true, Arc::new(release_date.finish())),
+        ("rulings", true, Arc::new(rulings.finish())),
+        ("foreignNames", false, Arc::new(foreign_names.finish())),
+        ("printings", false, Arc::new(printings.finish())),
+        ("originalText", true, Arc::new(original_text.finish())),
+        ("originalType", true, Arc::new(original_type.finish())),
+        ("legalities", false, Arc::new(legalities.finish())),
+    ];
+
+    let fields: Vec<Field> = columns
+        .iter()
+        .map(|(name, nullable, array)| Field::new(*name, array.data_type().clone(), *nullable))
+        .collect();
+    let arrays = columns.into_iter().map(|(_, _, array)| array).collect();
+    RecordBatch::try_new(Arc::new(Schema::new(fields)), arrays)
+}
+
+/// Function to get the Arrow schema of the record batches built by `to_record_batch()`.
+pub fn schema() -> SchemaRef {
+    to_record_batch(&[])
+        .expect("an empty batch is always valid")
+        .schema()
+}
+
+/// Function to write cards as a Parquet file.
+///
+/// Without `properties`, the file is written with Parquet's defaults (no compression).
+///
+/// # Example
+/// ```rust
+/// use mtgsdk::cards::Card;
+/// use mtgsdk::export::arrow;
+///
+/// let cards = vec![Card { name: "Karn Liberated".to_string(), ..Default::default() }];
+/// let mut file = Vec::new();
+/// arrow::write_parquet(&mut file, &cards, None).unwrap();
+/// assert!(file.starts_with(b"PAR1"));
+/// ```
+///
+/// # Errors
+/// If the cards cannot be converted or written, it will return a `Err(ParquetError)`.
+pub fn write_parquet<W>(
+    writer: W,
+    cards: &[Card],
+    properties: Option<WriterProperties>,
+) -> Result<(), ParquetError>
+where
+    W: Write + Send,
+{
+    let mut parquet = ArrowWriter::try_new(writer, schema(), properties)?;
+
+    for chunk in cards.chunks(BATCH_SIZE) {
+        parquet.write(&to_record_batch(chunk)?)?;
+    }
+    parquet.close()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cards::{Card, ForeignNames, Legalities, Rulings};
+    use crate::export::arrow;
+    use crate::ids::MultiverseId;
+    use arrow_array::{Array, ListArray, StringArray, StructArray};
+
+    fn breya() -> Card {
+        Card {
+            name: "Breya, Etherium Shaper".to_string(),
+            cmc: 4.0,
+            colors: Some(
+                vec!["Red", "White", "Blue", "Black"]
+                    .into_iter()
+                    .map(String::from)
+                    .collect(),
+            ),
+            types: vec!["Artifact".to_string(), "Creature".to_string()]
+                .into_iter()
+                .collect(),
+            rulings: Some(vec![Rulings {
+                date: "2016-11-08".to_string(),
+                text: "Breya's last ability can be activated at any time.".to_string(),
+            }]),
+            foreign_names: vec![ForeignNames {
+                name: "Breya, Ätheriumformerin".to_string(),
+                text: String::new(),
+                type_field: None,
+                flavor: None,
+                image_url: None,
+                language: "German".to_string(),
+                multiverseid: Some(MultiverseId(419877)),
+            }],
+            legalities: vec![Legalities {
+                format: "Commander".to_string(),
+                legality: "Legal".to_string(),
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn nested_columns() {
+        let batch = arrow::to_record_batch(&[breya(), Card::default()]).unwrap();
+        assert_eq!(batch.num_rows(), 2);
+        assert_eq!(batch.schema(), arrow::schema());
+
+        let colors = batch
+            .column_by_name("colors")
+            .unwrap()
+            .as_any()
+            .downcast_ref::<ListArray>()
+            .unwrap();
+        assert_eq!(colors.value(0).len(), 4);
+        assert!(colors.is_null(1));
+
+        let legalities = batch
+            .column_by_name("legalities")
+            .unwrap()
+            .as_any()
+            .downcast_ref::<ListArray>()
+            .unwrap();
+        let legality = legalities.value(0);
+        let legality = legality.as_any().downcast_ref::<StructArray>().unwrap();
+        let format = legality
+            .column_by_name("format")
+            .unwrap()
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert_eq!(format.value(0), "Commander");
+    }
+
+    #[test]
+    fn write_parquet_file() {
+        let mut file = Vec::new();
+        arrow::write_parquet(&mut file, &[breya(), Card::default()], None).unwrap();
+        assert!(file.starts_with(b"PAR1"));
+        assert!(file.ends_with(b"PAR1"));
+    }
+}
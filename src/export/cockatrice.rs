@@ -0,0 +1,321 @@
+//! Write [Cockatrice](https://cockatrice.github.io/) card databases (`cards.xml`, version 4 schema).
+//!
+//! Every printing of a card is written as a `<set>` entry of a single `<card>`, so the same card can be passed
+//! once per set (e.g.: the result of a `cards::filter()` over several sets).
+#![allow(dead_code)]
+use super::escape_xml;
+use crate::cards::{self, format_name, gatherer_image_url, Card};
+use crate::sets::Set;
+use std::collections::{HashMap, HashSet};
+use std::io::{self, Write};
+
+// Cockatrice shows one main type, picked in this order
+const MAIN_TYPES: [&str; 7] = [
+    "Planeswalker",
+    "Creature",
+    "Land",
+    "Sorcery",
+    "Instant",
+    "Artifact",
+    "Enchantment",
+];
+
+// "{3}{U}{R/W}" becomes "3UR/W"
+fn mana_cost(cost: &str) -> String {
+    cost.chars().filter(|c| *c != '{' && *c != '}').collect()
+}
+
+// Color names in WUBRG order, as symbols (e.g.: "URW" becomes "WUR")
+fn color_symbols(colors: &Option<HashSet<String>>) -> String {
+    let colors = match colors {
+        Some(c) => c,
+        None => return String::new(),
+    };
+    ["W", "U", "B", "R", "G"]
+        .iter()
+        .filter(|symbol| {
+            colors
+                .iter()
+                .any(|name| cards::color_symbol(name) == Some(**symbol))
+        })
+        .copied()
+        .collect()
+}
+
+fn main_type(card: &Card) -> &str {
+    MAIN_TYPES
+        .iter()
+        .find(|t| card.types.contains(**t))
+        .copied()
+        .or_else(|| card.types.iter().next().map(String::as_str))
+        .unwrap_or_default()
+}
+
+// 0: lands, 1: other permanents, 2: creatures, 3: instants and sorceries
+fn table_row(card: &Card) -> u8 {
+    if card.types.contains("Land") {
+        0
+    } else if card.types.contains("Creature") {
+        2
+    } else if card.types.contains("Instant") || card.types.contains("Sorcery") {
+        3
+    } else {
+        1
+    }
+}
+
+fn picture_url(card: &Card) -> Option<String> {
+    card.image_url
+        .clone()
+        .or_else(|| card.multiverseid.map(gatherer_image_url))
+}
+
+fn write_set<W: Write>(writer: &mut W, set: &Set) -> io::Result<()> {
+    writeln!(writer, "    <set>")?;
+    writeln!(writer, "      <name>{}</name>", escape_xml(&set.code))?;
+    writeln!(
+        writer,
+        "      <longname>{}</longname>",
+        escape_xml(&set.name)
+    )?;
+    writeln!(
+        writer,
+        "      <settype>{}</settype>",
+        escape_xml(&format_name(&set.type_field))
+    )?;
+    if let Some(date) = set.release_date {
+        writeln!(
+            writer,
+            "      <releasedate>{}</releasedate>",
+            escape_xml(&date.to_string())
+        )?;
+    }
+    writeln!(writer, "    </set>")
+}
+
+fn write_printing<W: Write>(writer: &mut W, card: &Card) -> io::Result<()> {
+    let rarity = card.rarity.to_lowercase().replace("mythic rare", "mythic");
+    write!(
+        writer,
+        "      <set rarity=\"{}\" uuid=\"{}\" num=\"{}\"",
+        escape_xml(&rarity),
+        escape_xml(&card.id),
+        escape_xml(&card.number)
+    )?;
+    if let Some(id) = &card.multiverseid {
+        write!(writer, " muid=\"{}\"", id)?;
+    }
+    if let Some(url) = picture_url(card) {
+        write!(writer, " picurl=\"{}\"", escape_xml(&url))?;
+    }
+    writeln!(writer, ">{}</set>", escape_xml(&card.set_field))
+}
+
+// `printings` holds every printing of the same card, the first one gives the card's properties
+fn write_card<W: Write>(writer: &mut W, printings: &[&Card]) -> io::Result<()> {
+    let card = printings[0];
+
+    writeln!(writer, "    <card>")?;
+    writeln!(writer, "      <name>{}</name>", escape_xml(&card.name))?;
+    writeln!(writer, "      <text>{}</text>", escape_xml(&card.text))?;
+    writeln!(writer, "      <prop>")?;
+    writeln!(
+        writer,
+        "        <layout>{}</layout>",
+        escape_xml(&card.layout)
+    )?;
+    let side = if card.number.ends_with('b') {
+        "back"
+    } else {
+        "front"
+    };
+    writeln!(writer, "        <side>{}</side>", side)?;
+    writeln!(
+        writer,
+        "        <type>{}</type>",
+        escape_xml(&card.type_field)
+    )?;
+    writeln!(
+        writer,
+        "        <maintype>{}</maintype>",
+        escape_xml(main_type(card))
+    )?;
+    if let Some(cost) = &card.mana_cost {
+        writeln!(
+            writer,
+            "        <manacost>{}</manacost>",
+            escape_xml(&mana_cost(cost))
+        )?;
+    }
+    writeln!(writer, "        <cmc>{}</cmc>", card.cmc)?;
+    writeln!(
+        writer,
+        "        <colors>{}</colors>",
+        color_symbols(&card.colors)
+    )?;
+    writeln!(
+        writer,
+        "        <coloridentity>{}</coloridentity>",
+        color_symbols(&card.color_identity)
+    )?;
+    if let (Some(power), Some(toughness)) = (&card.power, &card.toughness) {
+        writeln!(
+            writer,
+            "        <pt>{}/{}</pt>",
+            escape_xml(power),
+            escape_xml(toughness)
+        )?;
+    }
+    if let Some(loyalty) = &card.loyalty {
+        writeln!(writer, "        <loyalty>{}</loyalty>", escape_xml(loyalty))?;
+    }
+    for legality in &card.legalities {
+        let format = legality.format.to_lowercase();
+        writeln!(
+            writer,
+            "        <format-{}>{}</format-{}>",
+            escape_xml(&format),
+            escape_xml(&legality.legality.to_lowercase()),
+            escape_xml(&format)
+        )?;
+    }
+    writeln!(writer, "      </prop>")?;
+
+    for printing in printings {
+        write_printing(writer, printing)?;
+    }
+
+    let mut related: Vec<&String> = card
+        .names
+        .iter()
+        .flatten()
+        .filter(|name| **name != card.name)
+        .collect();
+    related.sort();
+    for name in related {
+        writeln!(writer, "      <related>{}</related>", escape_xml(name))?;
+    }
+
+    writeln!(writer, "      <tablerow>{}</tablerow>", table_row(card))?;
+    writeln!(writer, "    </card>")
+}
+
+/// Function to write a Cockatrice card database.
+///
+/// Cards with the same name are written as a single card with one `<set>` entry per printing.
+///
+/// # Example
+/// ```rust
+/// use mtgsdk::cards::Card;
+/// use mtgsdk::export::cockatrice;
+/// use mtgsdk::sets::Set;
+///
+/// let sets = vec![Set { code: "KTK".parse().unwrap(), name: "Khans of Tarkir".to_string(), ..Default::default() }];
+/// let cards = vec![Card { name: "Narset, Enlightened Master".to_string(), set_field: "KTK".parse().unwrap(), ..Default::default() }];
+///
+/// let mut xml = Vec::new();
+/// cockatrice::write(&mut xml, &sets, &cards).unwrap();
+/// assert!(String::from_utf8(xml).unwrap().contains("<name>Narset, Enlightened Master</name>"));
+/// ```
+///
+/// # Errors
+/// If writing fails, it will return a `Err(std::io::Error)`.
+pub fn write<W: Write>(mut writer: W, sets: &[Set], cards: &[Card]) -> io::Result<()> {
+    writeln!(writer, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>")?;
+    writeln!(writer, "<cockatrice_carddatabase version=\"4\">")?;
+
+    writeln!(writer, "  <sets>")?;
+    for set in sets {
+        write_set(&mut writer, set)?;
+    }
+    writeln!(writer, "  </sets>")?;
+
+    // Group printings by name, keeping the order in which the cards were given
+    let mut grouped: Vec<Vec<&Card>> = Vec::new();
+    let mut positions: HashMap<&str, usize> = HashMap::new();
+    for card in cards {
+        match positions.get(card.name.as_str()) {
+            Some(i) => grouped[*i].push(card),
+            None => {
+                positions.insert(&card.name, grouped.len());
+                grouped.push(vec![card]);
+            }
+        }
+    }
+
+    writeln!(writer, "  <cards>")?;
+    for printings in grouped {
+        write_card(&mut writer, &printings)?;
+    }
+    writeln!(writer, "  </cards>")?;
+
+    writeln!(writer, "</cockatrice_carddatabase>")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cards::Card;
+    use crate::export::cockatrice;
+    use crate::ids::{CardId, MultiverseId, SetCode};
+    use crate::sets::Set;
+
+    #[test]
+    fn write_card_database() {
+        let sets = vec![Set {
+            code: SetCode::parse("ISD").unwrap(),
+            name: "Innistrad".to_string(),
+            type_field: "expansion".to_string(),
+            release_date: Some("2011-09-30".parse().unwrap()),
+            ..Default::default()
+        }];
+        let delver = Card {
+            name: "Delver of Secrets".to_string(),
+            layout: "transform".to_string(),
+            cmc: 1.0,
+            colors: Some(vec!["Blue".to_string()].into_iter().collect()),
+            type_field: "Creature — Human Wizard".to_string(),
+            types: vec!["Creature".to_string()].into_iter().collect(),
+            rarity: "Common".to_string(),
+            set_field: SetCode::parse("ISD").unwrap(),
+            text: "Reveal the top card & transform <this>.".to_string(),
+            number: "51a".to_string(),
+            power: Some("1".to_string()),
+            toughness: Some("1".to_string()),
+            id: CardId::from_raw("delver-isd".to_string()),
+            multiverseid: Some(MultiverseId(226749)),
+            names: Some(
+                vec![
+                    "Delver of Secrets".to_string(),
+                    "Insectile Aberration".to_string(),
+                ]
+                .into_iter()
+                .collect(),
+            ),
+            mana_cost: Some("{U}".to_string()),
+            ..Default::default()
+        };
+        let reprint = Card {
+            set_field: SetCode::parse("MID").unwrap(),
+            id: CardId::from_raw("delver-mid".to_string()),
+            multiverseid: None,
+            image_url: Some("https://example.com/delver.jpg".to_string()),
+            ..delver.clone()
+        };
+
+        let mut xml = Vec::new();
+        cockatrice::write(&mut xml, &sets, &[delver, reprint]).unwrap();
+        let xml = String::from_utf8(xml).unwrap();
+
+        assert!(xml.contains("<cockatrice_carddatabase version=\"4\">"));
+        assert!(xml.contains("<settype>Expansion</settype>"));
+        assert_eq!(xml.matches("<card>").count(), 1);
+        assert!(xml.contains("<text>Reveal the top card &amp; transform &lt;this&gt;.</text>"));
+        assert!(xml.contains("<manacost>U</manacost>"));
+        assert!(xml.contains("<colors>U</colors>"));
+        assert!(xml.contains("<pt>1/1</pt>"));
+        assert!(xml.contains("muid=\"226749\""));
+        assert!(xml.contains("picurl=\"https://example.com/delver.jpg\">MID</set>"));
+        assert!(xml.contains("<related>Insectile Aberration</related>"));
+        assert!(xml.contains("<tablerow>2</tablerow>"));
+    }
+}
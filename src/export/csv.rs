@@ -0,0 +1,465 @@
+//! Write cards and sets as CSV, one row per card or set.
+//!
+//! Columns are picked with `CardColumn` and `SetColumn`. Multi-valued fields are flattened into a single cell,
+//! values separated by `;` (e.g.: colors "White;Blue", legalities "Legacy:Legal;Modern:Legal").
+#![allow(dead_code)]
+use super::Error;
+use crate::cards::Card;
+use crate::sets::{Booster, Set};
+use futures::stream::{Stream, StreamExt};
+use http::StatusCode;
+use std::borrow::Borrow;
+use std::collections::HashSet;
+use std::io::{self, Write};
+
+/// Card fields available as CSV columns.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CardColumn {
+    Name,
+    Layout,
+    Cmc,
+    Colors,
+    ColorIdentity,
+    TypeField,
+    Supertypes,
+    Types,
+    Subtypes,
+    Rarity,
+    SetField,
+    SetName,
+    Text,
+    Artist,
+    Number,
+    Power,
+    Toughness,
+    Loyalty,
+    Id,
+    Multiverseid,
+    Names,
+    ManaCost,
+    ImageUrl,
+    Watermark,
+    Border,
+    ReleaseDate,
+    Printings,
+    OriginalText,
+    OriginalType,
+    Legalities,
+}
+
+impl CardColumn {
+    /// Every column, in the order of the `Card` fields.
+    pub const ALL: [CardColumn; 30] = [
+        CardColumn::Name,
+        CardColumn::Layout,
+        CardColumn::Cmc,
+        CardColumn::Colors,
+        CardColumn::ColorIdentity,
+        CardColumn::TypeField,
+        CardColumn::Supertypes,
+        CardColumn::Types,
+        CardColumn::Subtypes,
+        CardColumn::Rarity,
+        CardColumn::SetField,
+        CardColumn::SetName,
+        CardColumn::Text,
+        CardColumn::Artist,
+        CardColumn::Number,
+        CardColumn::Power,
+        CardColumn::Toughness,
+        CardColumn::Loyalty,
+        CardColumn::Id,
+        CardColumn::Multiverseid,
+        CardColumn::Names,
+        CardColumn::ManaCost,
+        CardColumn::ImageUrl,
+        CardColumn::Watermark,
+        CardColumn::Border,
+        CardColumn::ReleaseDate,
+        CardColumn::Printings,
+        CardColumn::OriginalText,
+        CardColumn::OriginalType,
+        CardColumn::Legalities,
+    ];
+
+    /// Column header, named as in the API's JSON.
+    pub fn header(&self) -> &'static str {
+        match self {
+            CardColumn::Name => "name",
+            CardColumn::Layout => "layout",
+            CardColumn::Cmc => "cmc",
+            CardColumn::Colors => "colors",
+            CardColumn::ColorIdentity => "colorIdentity",
+            CardColumn::TypeField => "type",
+            CardColumn::Supertypes => "supertypes",
+            CardColumn::Types => "types",
+            CardColumn::Subtypes => "subtypes",
+            CardColumn::Rarity => "rarity",
+            CardColumn::SetField => "set",
+            CardColumn::SetName => "setName",
+            CardColumn::Text => "text",
+            CardColumn::Artist => "artist",
+            CardColumn::Number => "number",
+            CardColumn::Power => "power",
+            CardColumn::Toughness => "toughness",
+            CardColumn::Loyalty => "loyalty",
+            CardColumn::Id => "id",
+            CardColumn::Multiverseid => "multiverseid",
+            CardColumn::Names => "names",
+            CardColumn::ManaCost => "manaCost",
+            CardColumn::ImageUrl => "imageUrl",
+            CardColumn::Watermark => "watermark",
+            CardColumn::Border => "border",
+            CardColumn::ReleaseDate => "releaseDate",
+            CardColumn::Printings => "printings",
+            CardColumn::OriginalText => "originalText",
+            CardColumn::OriginalType => "originalType",
+            CardColumn::Legalities => "legalities",
+        }
+    }
+
+    /// Cell value for `card`.
+    pub fn value(&self, card: &Card) -> String {
+        match self {
+            CardColumn::Name => card.name.clone(),
+            CardColumn::Layout => card.layout.clone(),
+            CardColumn::Cmc => card.cmc.to_string(),
+            CardColumn::Colors => join_colors(&card.colors),
+            CardColumn::ColorIdentity => join_colors(&card.color_identity),
+            CardColumn::TypeField => card.type_field.clone(),
+            CardColumn::Supertypes => join_optional(&card.supertypes),
+            CardColumn::Types => join(&card.types),
+            CardColumn::Subtypes => join_optional(&card.subtypes),
+            CardColumn::Rarity => card.rarity.clone(),
+            CardColumn::SetField => card.set_field.to_string(),
+            CardColumn::SetName => card.set_name.clone(),
+            CardColumn::Text => card.text.clone(),
+            CardColumn::Artist => card.artist.clone(),
+            CardColumn::Number => card.number.clone(),
+            CardColumn::Power => card.power.clone().unwrap_or_default(),
+            CardColumn::Toughness => card.toughness.clone().unwrap_or_default(),
+            CardColumn::Loyalty => card.loyalty.clone().unwrap_or_default(),
+            CardColumn::Id => card.id.to_string(),
+            CardColumn::Multiverseid => card
+                .multiverseid
+                .map(|id| id.to_string())
+                .unwrap_or_default(),
+            CardColumn::Names => join_optional(&card.names),
+            CardColumn::ManaCost => card.mana_cost.clone().unwrap_or_default(),
+            CardColumn::ImageUrl => card.image_url.clone().unwrap_or_default(),
+            CardColumn::Watermark => card.watermark.clone().unwrap_or_default(),
+            CardColumn::Border => card.border.clone().unwrap_or_default(),
+            CardColumn::ReleaseDate => card
+                .release_date
+                .map(|date| date.to_string())
+                .unwrap_or_default(),
+            CardColumn::Printings => join(&card.printings),
+            CardColumn::OriginalText => card.original_text.clone().unwrap_or_default(),
+            CardColumn::OriginalType => card.original_type.clone().unwrap_or_default(),
+            CardColumn::Legalities => card
+                .legalities
+                .iter()
+                .map(|l| format!("{}:{}", l.format, l.legality))
+                .collect::<Vec<String>>()
+                .join(";"),
+        }
+    }
+}
+
+/// Set fields available as CSV columns.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SetColumn {
+    Code,
+    Name,
+    TypeField,
+    Booster,
+    ReleaseDate,
+    Block,
+    OnlineOnly,
+    GathererCode,
+    OldCode,
+    MagicCardsInfoCode,
+    Border,
+    Expansion,
+    MkmName,
+    MkmId,
+}
+
+impl SetColumn {
+    /// Every column, in the order of the `Set` fields.
+    pub const ALL: [SetColumn; 14] = [
+        SetColumn::Code,
+        SetColumn::Name,
+        SetColumn::TypeField,
+        SetColumn::Booster,
+        SetColumn::ReleaseDate,
+        SetColumn::Block,
+        SetColumn::OnlineOnly,
+        SetColumn::GathererCode,
+        SetColumn::OldCode,
+        SetColumn::MagicCardsInfoCode,
+        SetColumn::Border,
+        SetColumn::Expansion,
+        SetColumn::MkmName,
+        SetColumn::MkmId,
+    ];
+
+    /// Column header, named as in the API's JSON.
+    pub fn header(&self) -> &'static str {
+        match self {
+            SetColumn::Code => "code",
+            SetColumn::Name => "name",
+            SetColumn::TypeField => "type",
+            SetColumn::Booster => "booster",
+            SetColumn::ReleaseDate => "releaseDate",
+            SetColumn::Block => "block",
+            SetColumn::OnlineOnly => "onlineOnly",
+            SetColumn::GathererCode => "gathererCode",
+            SetColumn::OldCode => "oldCode",
+            SetColumn::MagicCardsInfoCode => "magicCardsInfoCode",
+            SetColumn::Border => "border",
+            SetColumn::Expansion => "expansion",
+            SetColumn::MkmName => "mkmName",
+            SetColumn::MkmId => "mkmId",
+        }
+    }
+
+    /// Cell value for `set`.
+    pub fn value(&self, set: &Set) -> String {
+        match self {
+            SetColumn::Code => set.code.to_string(),
+            SetColumn::Name => set.name.clone(),
+            SetColumn::TypeField => set.type_field.clone(),
+            // Slots with a choice of sheets are joined with "|" (e.g.: "rare|mythic rare")
+            SetColumn::Booster => set
+                .booster
+                .iter()
+                .map(|b| match b {
+                    Booster::Single(s) => s.clone(),
+                    Booster::Multiple(m) => m.join("|"),
+                })
+                .collect::<Vec<String>>()
+                .join(";"),
+            SetColumn::ReleaseDate => set
+                .release_date
+                .map(|date| date.to_string())
+                .unwrap_or_default(),
+            SetColumn::Block => set.block.clone().unwrap_or_default(),
+            SetColumn::OnlineOnly => set.online_only.map(|o| o.to_string()).unwrap_or_default(),
+            SetColumn::GathererCode => set.gatherer_code.clone().unwrap_or_default(),
+            SetColumn::OldCode => set.old_code.clone().unwrap_or_default(),
+            SetColumn::MagicCardsInfoCode => set.magic_cards_info_code.clone().unwrap_or_default(),
+            SetColumn::Border => set.border.clone().unwrap_or_default(),
+            SetColumn::Expansion => set.expansion.clone().unwrap_or_default(),
+            SetColumn::MkmName => set.mkm_name.clone().unwrap_or_default(),
+            SetColumn::MkmId => set.mkm_id.map(|id| id.to_string()).unwrap_or_default(),
+        }
+    }
+}
+
+// Sorted, so the same card always gives the same cell
+fn join<T: AsRef<str>>(values: &HashSet<T>) -> String {
+    let mut values: Vec<&str> = values.iter().map(AsRef::as_ref).collect();
+    values.sort_unstable();
+    values.join(";")
+}
+
+fn join_optional(values: &Option<HashSet<String>>) -> String {
+    values.as_ref().map(join).unwrap_or_default()
+}
+
+// Colors keep the WUBRG order instead of the alphabetical one
+fn join_colors(colors: &Option<HashSet<String>>) -> String {
+    let colors = match colors {
+        Some(c) => c,
+        None => return String::new(),
+    };
+    ["White", "Blue", "Black", "Red", "Green"]
+        .iter()
+        .filter(|color| colors.contains(**color))
+        .copied()
+        .collect::<Vec<&str>>()
+        .join(";")
+}
+
+fn escape_csv(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn write_row<W, I>(writer: &mut W, cells: I) -> io::Result<()>
+where
+    W: Write,
+    I: IntoIterator<Item = String>,
+{
+    let row: Vec<String> = cells.into_iter().map(|cell| escape_csv(&cell)).collect();
+    writeln!(writer, "{}", row.join(","))
+}
+
+/// Function to write cards as CSV, header included.
+///
+/// # Example
+/// ```rust
+/// use mtgsdk::cards::Card;
+/// use mtgsdk::export::csv::{self, CardColumn};
+///
+/// let cards = vec![Card { name: "Karn Liberated".to_string(), cmc: 7.0, ..Default::default() }];
+/// let mut out = Vec::new();
+/// csv::write_cards(&mut out, &cards, &[CardColumn::Name, CardColumn::Cmc]).unwrap();
+/// assert_eq!(String::from_utf8(out).unwrap(), "name,cmc\nKarn Liberated,7\n");
+/// ```
+///
+/// # Errors
+/// If writing fails, it will return a `Err(std::io::Error)`.
+pub fn write_cards<W, I>(mut writer: W, cards: I, columns: &[CardColumn]) -> io::Result<()>
+where
+    W: Write,
+    I: IntoIterator,
+    I::Item: Borrow<Card>,
+{
+    write_row(&mut writer, columns.iter().map(|c| c.header().to_string()))?;
+    for card in cards {
+        write_row(&mut writer, columns.iter().map(|c| c.value(card.borrow())))?;
+    }
+    Ok(())
+}
+
+/// Function to write a stream of cards as CSV, header included (e.g.: `cards::filter().stream()`).
+///
+/// Returns the number of cards written.
+///
+/// # Example
+/// ```rust
+/// use mtgsdk::cards;
+/// use mtgsdk::export::csv::{self, CardColumn};
+/// async {
+///     let mut out = Vec::new();
+///     let written = csv::write_card_stream(&mut out, cards::filter().set_field("KTK").stream(), &CardColumn::ALL).await;
+///     assert!(written.unwrap() > 0);
+/// };
+/// ```
+///
+/// # Errors
+/// If a page cannot be fetched, it will return a `Err(Error::Api(StatusCode))`.
+/// If writing fails, it will return a `Err(Error::Io(std::io::Error))`.
+/// In both cases, the cards received up to that point are already written.
+pub async fn write_card_stream<W, S>(
+    mut writer: W,
+    cards: S,
+    columns: &[CardColumn],
+) -> Result<usize, Error>
+where
+    W: Write,
+    S: Stream<Item = Result<Card, StatusCode>>,
+{
+    write_row(&mut writer, columns.iter().map(|c| c.header().to_string()))?;
+
+    let mut cards = Box::pin(cards);
+    let mut written = 0;
+    while let Some(card) = cards.next().await {
+        let card = card?;
+        write_row(&mut writer, columns.iter().map(|c| c.value(&card)))?;
+        written += 1;
+    }
+    Ok(written)
+}
+
+/// Function to write sets as CSV, header included.
+///
+/// # Errors
+/// If writing fails, it will return a `Err(std::io::Error)`.
+pub fn write_sets<W, I>(mut writer: W, sets: I, columns: &[SetColumn]) -> io::Result<()>
+where
+    W: Write,
+    I: IntoIterator,
+    I::Item: Borrow<Set>,
+{
+    write_row(&mut writer, columns.iter().map(|c| c.header().to_string()))?;
+    for set in sets {
+        write_row(&mut writer, columns.iter().map(|c| c.value(set.borrow())))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cards::{Card, Legalities};
+    use crate::export::csv::{self, CardColumn, SetColumn};
+    use crate::export::Error;
+    use crate::ids::SetCode;
+    use crate::sets::{Booster, Set};
+    use futures::executor::block_on;
+    use futures::stream;
+    use http::StatusCode;
+
+    fn narset() -> Card {
+        Card {
+            name: "Narset, Enlightened Master".to_string(),
+            colors: Some(
+                vec!["Red".to_string(), "White".to_string(), "Blue".to_string()]
+                    .into_iter()
+                    .collect(),
+            ),
+            text: "Whenever Narset attacks, exile the top four cards of your library.".to_string(),
+            legalities: vec![
+                Legalities {
+                    format: "Commander".to_string(),
+                    legality: "Legal".to_string(),
+                },
+                Legalities {
+                    format: "Modern".to_string(),
+                    legality: "Legal".to_string(),
+                },
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn write_cards_flattened() {
+        let mut out = Vec::new();
+        let columns = [CardColumn::Name, CardColumn::Colors, CardColumn::Legalities];
+        csv::write_cards(&mut out, vec![narset()], &columns).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "name,colors,legalities\n\
+             \"Narset, Enlightened Master\",White;Blue;Red,Commander:Legal;Modern:Legal\n"
+        );
+    }
+
+    #[test]
+    fn write_sets_flattened() {
+        let set = Set {
+            code: SetCode::parse("KTK").unwrap(),
+            booster: vec![
+                Booster::Multiple(vec!["rare".to_string(), "mythic rare".to_string()]),
+                Booster::Single("common".to_string()),
+            ],
+            ..Default::default()
+        };
+        let mut out = Vec::new();
+        csv::write_sets(&mut out, &[set], &[SetColumn::Code, SetColumn::Booster]).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "code,booster\nKTK,rare|mythic rare;common\n"
+        );
+    }
+
+    #[test]
+    fn write_stream_stops_on_error() {
+        let cards = stream::iter(vec![Ok(narset()), Err(StatusCode::SERVICE_UNAVAILABLE)]);
+        let mut out = Vec::new();
+        let written = block_on(csv::write_card_stream(&mut out, cards, &[CardColumn::Name]));
+
+        assert!(matches!(
+            written,
+            Err(Error::Api(StatusCode::SERVICE_UNAVAILABLE))
+        ));
+        assert_eq!(String::from_utf8(out).unwrap().lines().count(), 2);
+    }
+}
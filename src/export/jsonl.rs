@@ -0,0 +1,110 @@
+//! Write cards and sets as [JSON Lines](https://jsonlines.org/), one JSON object per line.
+//!
+//! Objects are written with the same field names as the API's JSON, so each line can be read back as a `Card` or a `Set`.
+#![allow(dead_code)]
+use super::Error;
+use crate::cards::Card;
+use futures::stream::{Stream, StreamExt};
+use http::StatusCode;
+use serde::Serialize;
+use std::io::{self, Write};
+
+fn write_line<W: Write, T: Serialize>(writer: &mut W, item: &T) -> io::Result<()> {
+    serde_json::to_writer(&mut *writer, item)?;
+    writeln!(writer)
+}
+
+/// Function to write cards or sets as JSON Lines.
+///
+/// # Example
+/// ```rust
+/// use mtgsdk::export::jsonl;
+/// use mtgsdk::sets::Set;
+///
+/// let sets = vec![Set { code: "DOM".parse().unwrap(), name: "Dominaria".to_string(), ..Default::default() }];
+/// let mut out = Vec::new();
+/// jsonl::write(&mut out, &sets).unwrap();
+/// assert!(String::from_utf8(out).unwrap().starts_with("{\"code\":\"DOM\""));
+/// ```
+///
+/// # Errors
+/// If writing fails, it will return a `Err(std::io::Error)`.
+pub fn write<W, I>(mut writer: W, items: I) -> io::Result<()>
+where
+    W: Write,
+    I: IntoIterator,
+    I::Item: Serialize,
+{
+    for item in items {
+        write_line(&mut writer, &item)?;
+    }
+    Ok(())
+}
+
+/// Function to write a stream of cards as JSON Lines (e.g.: `cards::filter().stream()`).
+///
+/// Returns the number of cards written.
+///
+/// # Example
+/// ```rust
+/// use mtgsdk::cards;
+/// use mtgsdk::export::jsonl;
+/// async {
+///     let mut out = Vec::new();
+///     let written = jsonl::write_card_stream(&mut out, cards::filter().name("Karn").stream()).await;
+///     assert!(written.unwrap() > 0);
+/// };
+/// ```
+///
+/// # Errors
+/// If a page cannot be fetched, it will return a `Err(Error::Api(StatusCode))`.
+/// If writing fails, it will return a `Err(Error::Io(std::io::Error))`.
+/// In both cases, the cards received up to that point are already written.
+pub async fn write_card_stream<W, S>(mut writer: W, cards: S) -> Result<usize, Error>
+where
+    W: Write,
+    S: Stream<Item = Result<Card, StatusCode>>,
+{
+    let mut cards = Box::pin(cards);
+    let mut written = 0;
+    while let Some(card) = cards.next().await {
+        write_line(&mut writer, &card?)?;
+        written += 1;
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cards::Card;
+    use crate::export::jsonl;
+    use futures::executor::block_on;
+    use futures::stream;
+
+    #[test]
+    fn round_trip() {
+        let cards = vec![
+            Card {
+                name: "Karn Liberated".to_string(),
+                ..Default::default()
+            },
+            Card {
+                name: "Karn, Scion of Urza".to_string(),
+                ..Default::default()
+            },
+        ];
+        let mut out = Vec::new();
+        let written = block_on(jsonl::write_card_stream(
+            &mut out,
+            stream::iter(cards.clone().into_iter().map(Ok)),
+        ));
+        assert_eq!(written.unwrap(), 2);
+
+        let read: Vec<Card> = String::from_utf8(out)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(read, cards);
+    }
+}
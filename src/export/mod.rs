@@ -0,0 +1,63 @@
+//! Write `cards::Card` and `sets::Set` to other formats.
+//!
+//! - `cockatrice` writes the [Cockatrice](https://cockatrice.github.io/) `cards.xml` card database;
+//! - `csv` writes cards and sets as CSV, with a choice of columns;
+//! - `jsonl` writes cards and sets as JSON Lines;
+//! - `arrow` converts cards into Arrow record batches and writes Parquet files (requires the `arrow` feature).
+//!
+//! `csv` and `jsonl` also take the cards straight from the API through `cards::Where::stream()`.
+use http::StatusCode;
+use std::{fmt, io};
+
+#[cfg(feature = "arrow")]
+pub mod arrow;
+pub mod cockatrice;
+pub mod csv;
+pub mod jsonl;
+
+/// Error when exporting cards fetched from the API.
+#[derive(Debug)]
+pub enum Error {
+    /// The cards could not be fetched.
+    Api(StatusCode),
+    /// The cards could not be written.
+    Io(io::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Api(status) => write!(f, "API call failed: {}", status),
+            Error::Io(e) => write!(f, "write failed: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<StatusCode> for Error {
+    fn from(status: StatusCode) -> Self {
+        Error::Api(status)
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
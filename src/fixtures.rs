@@ -0,0 +1,66 @@
+//! Cards and sets shared by the tests (the fake transport is `client::tests::Fake`).
+//!
+//! Each test sets what it looks at on top of these, e.g.: `Card { cmc: 2.0, ..fixtures::card("Tarmogoyf") }`.
+use crate::cards::Card;
+use crate::ids::{CardId, SetCode};
+use crate::sets::Set;
+use std::iter::FromIterator;
+
+// A card with only its name
+pub(crate) fn card(name: &str) -> Card {
+    Card {
+        name: name.to_string(),
+        ..Default::default()
+    }
+}
+
+// A printing of a card: its id and number (from `n`) and its set
+pub(crate) fn printing(n: u32, name: &str, set: &str) -> Card {
+    Card {
+        id: card_id(n),
+        layout: "normal".to_string(),
+        set_field: set_code(set),
+        number: n.to_string(),
+        ..card(name)
+    }
+}
+
+// "00000000-0000-0000-0000-000000000042" for 42
+pub(crate) fn card_id(n: u32) -> CardId {
+    CardId::parse(&format!("00000000-0000-0000-0000-{:012}", n)).unwrap()
+}
+
+pub(crate) fn set_code(code: &str) -> SetCode {
+    SetCode::parse(code).unwrap()
+}
+
+// Into a `Vec` or a `HashSet`, as the field needs
+pub(crate) fn set_codes<C: FromIterator<SetCode>>(codes: &[&str]) -> C {
+    codes.iter().map(|code| set_code(code)).collect()
+}
+
+// Into a `Vec` or a `HashSet`, as the field needs
+pub(crate) fn strings<C: FromIterator<String>>(values: &[&str]) -> C {
+    values.iter().map(|value| value.to_string()).collect()
+}
+
+pub(crate) fn set(code: &str, type_field: &str, block: Option<&str>, release_date: &str) -> Set {
+    Set {
+        code: set_code(code),
+        type_field: type_field.to_string(),
+        block: block.map(String::from),
+        release_date: Some(release_date.parse().unwrap()),
+        ..Default::default()
+    }
+}
+
+// Codes of the sets, in the same order
+pub(crate) fn codes(sets: &[&Set]) -> Vec<String> {
+    sets.iter().map(|set| set.code.to_string()).collect()
+}
+
+// Body of a page of cards (`{"cards": [...]}`) with the given names
+pub(crate) fn page(names: &[&str]) -> String {
+    let cards: Vec<Card> = names.iter().map(|name| card(name)).collect();
+    serde_json::json!({ "cards": cards }).to_string()
+}
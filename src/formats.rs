@@ -1,7 +1,8 @@
 //! Get game formats (e.g.: Standard, Modern, Onslaught Block).
 #![allow(dead_code)]
+use crate::client::Client;
 use crate::query_builder;
-use reqwest::StatusCode;
+use http::StatusCode;
 use serde::Deserialize;
 use std::collections::HashSet;
 
@@ -26,7 +27,27 @@ pub struct RootAll {
 /// If the call fails, it will return a `Err(StatusCode)`.
 /// To see the possible return values, check the [API docs](https://docs.magicthegathering.io/#documentationerrors).
 pub async fn all() -> Result<HashSet<String>, StatusCode> {
-    let formats: Result<RootAll, StatusCode> = query_builder::all("formats").await;
+    all_with(&Client::default()).await
+}
+
+/// Function to get all formats with the given `Client` (see `client`).
+///
+/// # Example
+/// ```rust
+/// use mtgsdk::client::Client;
+/// use mtgsdk::formats;
+/// async {
+///    let client = Client::default();
+///    let formats = formats::all_with(&client).await;
+///    assert!(!formats.unwrap().is_empty());
+/// };
+///```
+///
+/// # Errors
+/// If the call fails, it will return a `Err(StatusCode)`.
+/// To see the possible return values, check the [API docs](https://docs.magicthegathering.io/#documentationerrors).
+pub async fn all_with(client: &Client) -> Result<HashSet<String>, StatusCode> {
+    let formats: Result<RootAll, StatusCode> = query_builder::all(client, "formats").await;
 
     match formats {
         Ok(t) => Ok(t.formats),
@@ -0,0 +1,298 @@
+//! Typed identifiers for cards and sets.
+//!
+//! The API names cards in two ways, and `ids` gives each one its own type:
+//! - `CardId`: the card's unique `id` (a SHA1 hash or a UUID), one per printing and language;
+//! - `MultiverseId`: the card's Gatherer id, which online-only cards and promos usually do not have.
+//!
+//! Sets are named by their `SetCode` (e.g.: "KTK").
+//!
+//! `parse()` validates the text, while deserializing only normalizes it (as the data comes from the API or a dump).
+//! A `SetCode` can also be made from a `&str` (e.g.: for `sets::find("dom")`), which normalizes it the same way.
+//! ```rust
+//! use mtgsdk::ids::{CardId, MultiverseId, SetCode};
+//!
+//! assert!(CardId::parse("5f8287b1-5bb6-5f4f-ad17-316a40d5bb0c").is_ok());
+//! assert_eq!(MultiverseId::parse("386616"), Ok(MultiverseId(386616)));
+//! assert_eq!(SetCode::parse("ktk").unwrap(), "KTK");
+//! assert!(SetCode::parse("not a set").is_err());
+//! ```
+use serde::de::{self, Deserializer, Visitor};
+use serde::{Deserialize, Serialize, Serializer};
+use std::convert::TryFrom;
+use std::fmt;
+use std::ops::Deref;
+use std::str::FromStr;
+
+/// Error when the text is not a valid identifier.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseIdError {
+    kind: &'static str,
+    input: String,
+}
+
+impl ParseIdError {
+    fn new(kind: &'static str, input: &str) -> Self {
+        ParseIdError {
+            kind,
+            input: input.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for ParseIdError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid {}: {:?}", self.kind, self.input)
+    }
+}
+
+impl std::error::Error for ParseIdError {}
+
+/// Unique `id` of a card (e.g.: "5f8287b1-5bb6-5f4f-ad17-316a40d5bb0c").
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CardId(String);
+
+impl CardId {
+    /// Validates a card id: either a SHA1 hash (40 hexadecimal digits) or a UUID. It is kept in lowercase.
+    ///
+    /// # Errors
+    /// If the text is neither, it will return a `Err(ParseIdError)`.
+    pub fn parse(input: &str) -> Result<Self, ParseIdError> {
+        let id = input.trim().to_ascii_lowercase();
+        let hex = |part: &str| part.chars().all(|c| c.is_ascii_hexdigit());
+
+        let parts: Vec<&str> = id.split('-').collect();
+        let lengths: Vec<usize> = parts.iter().map(|part| part.len()).collect();
+        let valid =
+            parts.iter().all(|part| hex(part)) && (lengths == [40] || lengths == [8, 4, 4, 4, 12]);
+
+        if valid {
+            Ok(CardId(id))
+        } else {
+            Err(ParseIdError::new("card id", input))
+        }
+    }
+
+    // Ids that are not from the API (e.g.: the faces of an imported card)
+    pub(crate) fn from_raw(id: String) -> Self {
+        CardId(id)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Gatherer id of a card (e.g.: 386616).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct MultiverseId(pub u64);
+
+impl MultiverseId {
+    /// Validates a multiverse id: a positive integer.
+    ///
+    /// # Errors
+    /// If the text is not, it will return a `Err(ParseIdError)`.
+    pub fn parse(input: &str) -> Result<Self, ParseIdError> {
+        match input.trim().parse() {
+            Ok(0) | Err(_) => Err(ParseIdError::new("multiverse id", input)),
+            Ok(id) => Ok(MultiverseId(id)),
+        }
+    }
+}
+
+/// Code of a set (e.g.: "KTK"), kept in uppercase.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SetCode(String);
+
+impl SetCode {
+    /// Validates a set code: 2 to 8 letters, digits or `_`. It is kept in uppercase.
+    ///
+    /// # Errors
+    /// If the text is not, it will return a `Err(ParseIdError)`.
+    pub fn parse(input: &str) -> Result<Self, ParseIdError> {
+        let code = input.trim();
+        let valid = (2..=8).contains(&code.len())
+            && code.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+
+        if valid {
+            Ok(SetCode(code.to_ascii_uppercase()))
+        } else {
+            Err(ParseIdError::new("set code", input))
+        }
+    }
+
+    // Codes that are not from the API
+    pub(crate) fn from_raw(code: &str) -> Self {
+        SetCode(code.to_ascii_uppercase())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+// `CardId` and `SetCode` are used as strings: printed, compared and borrowed as such
+macro_rules! string_id {
+    ($id:ident) => {
+        impl fmt::Display for $id {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+
+        impl Deref for $id {
+            type Target = str;
+            fn deref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl AsRef<str> for $id {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl FromStr for $id {
+            type Err = ParseIdError;
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                $id::parse(s)
+            }
+        }
+
+        impl PartialEq<str> for $id {
+            fn eq(&self, other: &str) -> bool {
+                self.0 == other
+            }
+        }
+
+        impl PartialEq<&str> for $id {
+            fn eq(&self, other: &&str) -> bool {
+                self.0 == *other
+            }
+        }
+
+        impl Serialize for $id {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(&self.0)
+            }
+        }
+    };
+}
+
+string_id!(CardId);
+string_id!(SetCode);
+
+/// Normalizes the code (trimmed, in uppercase) without validating it: an unknown code is not found by the API.
+impl From<&str> for SetCode {
+    fn from(code: &str) -> Self {
+        SetCode::from_raw(code.trim())
+    }
+}
+
+impl From<&SetCode> for SetCode {
+    fn from(code: &SetCode) -> Self {
+        code.clone()
+    }
+}
+
+impl<'de> Deserialize<'de> for CardId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(CardId::from_raw)
+    }
+}
+
+impl<'de> Deserialize<'de> for SetCode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(|code| SetCode::from_raw(&code))
+    }
+}
+
+impl fmt::Display for MultiverseId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for MultiverseId {
+    type Err = ParseIdError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        MultiverseId::parse(s)
+    }
+}
+
+impl From<u64> for MultiverseId {
+    fn from(id: u64) -> Self {
+        MultiverseId(id)
+    }
+}
+
+impl Serialize for MultiverseId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(self.0)
+    }
+}
+
+// The API sends the multiverse id as a string in cards and as a number in foreign names: take both
+impl<'de> Deserialize<'de> for MultiverseId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct MultiverseIdVisitor;
+
+        impl<'de> Visitor<'de> for MultiverseIdVisitor {
+            type Value = MultiverseId;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a multiverse id, as a number or a string")
+            }
+
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<MultiverseId, E> {
+                Ok(MultiverseId(v))
+            }
+
+            fn visit_i64<E: de::Error>(self, v: i64) -> Result<MultiverseId, E> {
+                match u64::try_from(v) {
+                    Ok(v) => Ok(MultiverseId(v)),
+                    Err(_) => Err(E::invalid_value(de::Unexpected::Signed(v), &self)),
+                }
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<MultiverseId, E> {
+                match v.trim().parse() {
+                    Ok(v) => Ok(MultiverseId(v)),
+                    Err(_) => Err(E::invalid_value(de::Unexpected::Str(v), &self)),
+                }
+            }
+        }
+
+        deserializer.deserialize_any(MultiverseIdVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ids::{CardId, MultiverseId, SetCode};
+
+    #[test]
+    fn parse_ids() {
+        assert!(
+            CardId::parse("5F8287B1-5BB6-5F4F-AD17-316A40D5BB0C").unwrap()
+                == "5f8287b1-5bb6-5f4f-ad17-316a40d5bb0c"
+        );
+        assert!(CardId::parse("2c02d8bbd4ee3c1ed3bca0c8bf7bbc3fb41cbb2e").is_ok());
+        assert!(CardId::parse("386616").is_err());
+        assert!(MultiverseId::parse("0").is_err());
+        assert!(MultiverseId::parse("-1").is_err());
+        assert_eq!(SetCode::parse(" dd3_jvc ").unwrap(), "DD3_JVC");
+        assert!(SetCode::parse("K").is_err());
+        assert_eq!(SetCode::from(" dom "), "DOM");
+    }
+
+    #[test]
+    fn serde_ids() {
+        let ids: Vec<MultiverseId> = serde_json::from_str(r#"[386616, "409574"]"#).unwrap();
+        assert_eq!(ids, vec![MultiverseId(386616), MultiverseId(409574)]);
+        assert_eq!(serde_json::to_string(&ids).unwrap(), "[386616,409574]");
+
+        let code: SetCode = serde_json::from_str(r#""pMEI""#).unwrap();
+        assert_eq!(serde_json::to_string(&code).unwrap(), r#""PMEI""#);
+    }
+}
@@ -0,0 +1,17 @@
+//! Convert third-party card databases into `cards::Card` and `sets::Set`.
+//!
+//! - `mtgjson` reads the [MTGJSON](https://mtgjson.com/) `AllPrintings.json` dump;
+//! - `scryfall` reads the [Scryfall](https://scryfall.com/docs/api/bulk-data) bulk-data files.
+use crate::cards::format_name;
+
+pub mod mtgjson;
+pub mod scryfall;
+
+// The API spells out "Mythic Rare", other sources use "mythic"
+fn rarity_name(rarity: &str) -> String {
+    if rarity.eq_ignore_ascii_case("mythic") {
+        String::from("Mythic Rare")
+    } else {
+        format_name(rarity)
+    }
+}
@@ -0,0 +1,446 @@
+//! Read [MTGJSON](https://mtgjson.com/) `AllPrintings.json` files.
+//!
+//! The dump is parsed one set at a time, so the whole file never needs to fit in memory:
+//! use `for_each_set()` to process the sets as they are read, or `load()` to gather them all in an `offline::Store`.
+//!
+//! MTGJSON objects are converted into the same `cards::Card` and `sets::Set` structures returned by the API
+//! (e.g.: colors become "Blue" instead of "U", legalities become a list of `cards::Legalities`).
+#![allow(dead_code)]
+use super::rarity_name;
+use crate::cards::{
+    self, format_name, gatherer_image_url, Card, ForeignNames, Legalities, Rulings,
+};
+use crate::date::Date;
+use crate::ids::{CardId, MultiverseId, SetCode};
+use crate::offline::Store;
+use crate::sets::{Booster, Set};
+use serde::de::{DeserializeSeed, Deserializer, IgnoredAny, MapAccess, Visitor};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use std::io::{BufReader, Read};
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct MtgJsonSet {
+    code: SetCode,
+    name: String,
+    #[serde(rename = "type")]
+    type_field: String,
+    release_date: Date,
+    block: Option<String>,
+    is_online_only: Option<bool>,
+    code_v3: Option<String>,
+    mtgo_code: Option<String>,
+    mcm_name: Option<String>,
+    mcm_id: Option<u32>,
+    booster: HashMap<String, MtgJsonBooster>,
+    cards: Vec<MtgJsonCard>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+struct MtgJsonBooster {
+    boosters: Vec<MtgJsonBoosterContents>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+struct MtgJsonBoosterContents {
+    contents: BTreeMap<String, u32>,
+    weight: u64,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct MtgJsonIdentifiers {
+    multiverse_id: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct MtgJsonForeignData {
+    language: String,
+    name: String,
+    text: Option<String>,
+    #[serde(rename = "type")]
+    type_field: Option<String>,
+    flavor_text: Option<String>,
+    multiverse_id: Option<MultiverseId>,
+    identifiers: MtgJsonIdentifiers,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct MtgJsonCard {
+    name: String,
+    face_name: Option<String>,
+    layout: String,
+    mana_value: Option<f64>,
+    converted_mana_cost: Option<f64>,
+    colors: Vec<String>,
+    color_identity: Vec<String>,
+    #[serde(rename = "type")]
+    type_field: String,
+    supertypes: Vec<String>,
+    types: Vec<String>,
+    subtypes: Vec<String>,
+    rarity: String,
+    text: Option<String>,
+    artist: Option<String>,
+    number: String,
+    power: Option<String>,
+    toughness: Option<String>,
+    loyalty: Option<String>,
+    uuid: CardId,
+    identifiers: MtgJsonIdentifiers,
+    mana_cost: Option<String>,
+    variations: Vec<String>,
+    watermark: Option<String>,
+    border_color: Option<String>,
+    original_release_date: Option<Date>,
+    rulings: Vec<Rulings>,
+    foreign_data: Vec<MtgJsonForeignData>,
+    printings: Vec<SetCode>,
+    original_text: Option<String>,
+    original_type: Option<String>,
+    legalities: BTreeMap<String, String>,
+}
+
+// The API leaves out empty lists, so they become `None`
+fn non_empty<T, C>(values: Vec<T>) -> Option<C>
+where
+    C: std::iter::FromIterator<T>,
+{
+    if values.is_empty() {
+        None
+    } else {
+        Some(values.into_iter().collect())
+    }
+}
+
+fn color_names(symbols: Vec<String>) -> Option<HashSet<String>> {
+    non_empty(
+        symbols
+            .into_iter()
+            .map(|s| cards::color_name(&s).map(String::from).unwrap_or(s))
+            .collect(),
+    )
+}
+
+fn convert_booster(booster: &HashMap<String, MtgJsonBooster>) -> Vec<Booster> {
+    // The most common configuration of the regular booster stands for the whole set
+    let contents = booster
+        .get("default")
+        .and_then(|b| b.boosters.iter().max_by_key(|c| c.weight));
+
+    match contents {
+        Some(c) => c
+            .contents
+            .iter()
+            .flat_map(|(sheet, count)| (0..*count).map(move |_| Booster::Single(sheet.clone())))
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+fn convert_foreign_data(data: MtgJsonForeignData) -> ForeignNames {
+    let multiverseid = data.multiverse_id.or_else(|| {
+        data.identifiers
+            .multiverse_id
+            .as_ref()
+            .and_then(|id| id.parse().ok())
+    });
+
+    ForeignNames {
+        name: data.name,
+        text: data.text.unwrap_or_default(),
+        type_field: data.type_field,
+        flavor: data.flavor_text,
+        image_url: multiverseid.map(gatherer_image_url),
+        language: data.language,
+        multiverseid,
+    }
+}
+
+fn convert_card(card: MtgJsonCard, set: &MtgJsonSet) -> Card {
+    // MTGJSON names split and double-faced cards "Fire // Ice", the API names each face and lists them in `names`
+    let names = match card.face_name {
+        Some(_) => Some(card.name.split(" // ").map(String::from).collect()),
+        None => None,
+    };
+    let multiverseid = card
+        .identifiers
+        .multiverse_id
+        .as_ref()
+        .and_then(|id| id.parse().ok());
+
+    Card {
+        name: card.face_name.unwrap_or(card.name),
+        layout: card.layout,
+        cmc: card
+            .mana_value
+            .or(card.converted_mana_cost)
+            .unwrap_or_default(),
+        colors: color_names(card.colors),
+        color_identity: color_names(card.color_identity),
+        type_field: card.type_field,
+        supertypes: non_empty(card.supertypes),
+        types: card.types.into_iter().collect(),
+        subtypes: non_empty(card.subtypes),
+        rarity: rarity_name(&card.rarity),
+        set_field: set.code.clone(),
+        set_name: set.name.clone(),
+        text: card.text.unwrap_or_default(),
+        artist: card.artist.unwrap_or_default(),
+        number: card.number,
+        power: card.power,
+        toughness: card.toughness,
+        loyalty: card.loyalty,
+        id: card.uuid,
+        image_url: multiverseid.map(gatherer_image_url),
+        multiverseid,
+        names,
+        mana_cost: card.mana_cost,
+        variations: non_empty(card.variations),
+        watermark: card.watermark,
+        border: card.border_color,
+        release_date: card.original_release_date,
+        rulings: non_empty(card.rulings),
+        foreign_names: card
+            .foreign_data
+            .into_iter()
+            .map(convert_foreign_data)
+            .collect(),
+        printings: card.printings.into_iter().collect(),
+        original_text: card.original_text,
+        original_type: card.original_type,
+        legalities: card
+            .legalities
+            .into_iter()
+            .map(|(format, legality)| Legalities {
+                format: format_name(&format),
+                legality,
+            })
+            .collect(),
+    }
+}
+
+fn convert_set(mut set: MtgJsonSet) -> (Set, Vec<Card>) {
+    let mtgjson_cards = std::mem::take(&mut set.cards);
+    let cards = mtgjson_cards
+        .into_iter()
+        .map(|card| convert_card(card, &set))
+        .collect();
+
+    let converted = Set {
+        booster: convert_booster(&set.booster),
+        code: set.code,
+        name: set.name,
+        type_field: set.type_field,
+        release_date: Some(set.release_date),
+        block: set.block,
+        online_only: set.is_online_only,
+        gatherer_code: None,
+        old_code: set.mtgo_code,
+        magic_cards_info_code: set.code_v3,
+        border: None,
+        expansion: None,
+        mkm_name: set.mcm_name,
+        mkm_id: set.mcm_id,
+    };
+
+    (converted, cards)
+}
+
+// Visits the root object, skipping everything but "data"
+struct RootVisitor<'f, F> {
+    f: &'f mut F,
+}
+
+impl<'de, 'f, F> Visitor<'de> for RootVisitor<'f, F>
+where
+    F: FnMut(Set, Vec<Card>),
+{
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an MTGJSON AllPrintings object")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<(), A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        while let Some(key) = map.next_key::<String>()? {
+            if key == "data" {
+                map.next_value_seed(DataSeed { f: &mut *self.f })?;
+            } else {
+                map.next_value::<IgnoredAny>()?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// Visits "data", handing each set over as soon as it is parsed
+struct DataSeed<'f, F> {
+    f: &'f mut F,
+}
+
+impl<'de, 'f, F> DeserializeSeed<'de> for DataSeed<'f, F>
+where
+    F: FnMut(Set, Vec<Card>),
+{
+    type Value = ();
+
+    fn deserialize<D>(self, deserializer: D) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de, 'f, F> Visitor<'de> for DataSeed<'f, F>
+where
+    F: FnMut(Set, Vec<Card>),
+{
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a map of set codes to MTGJSON sets")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<(), A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        while let Some((_, set)) = map.next_entry::<IgnoredAny, MtgJsonSet>()? {
+            let (set, cards) = convert_set(set);
+            (self.f)(set, cards);
+        }
+        Ok(())
+    }
+}
+
+/// Function to read an `AllPrintings.json` file one set at a time.
+///
+/// `f` is called with each set and its cards as soon as they are read.
+///
+/// # Example
+/// ```rust
+/// use mtgsdk::import::mtgjson;
+///
+/// let json = r#"{"meta": {}, "data": {"DOM": {"code": "DOM", "name": "Dominaria", "type": "expansion", "releaseDate": "2018-04-27", "cards": []}}}"#;
+/// let mut names = Vec::new();
+/// mtgjson::for_each_set(json.as_bytes(), |set, _cards| names.push(set.name)).unwrap();
+/// assert_eq!(names, vec!["Dominaria"]);
+/// ```
+///
+/// # Errors
+/// If the file cannot be read or is not a valid MTGJSON file, it will return a `Err(serde_json::Error)`.
+pub fn for_each_set<R, F>(reader: R, mut f: F) -> serde_json::Result<()>
+where
+    R: Read,
+    F: FnMut(Set, Vec<Card>),
+{
+    let mut deserializer = serde_json::Deserializer::from_reader(BufReader::new(reader));
+    deserializer.deserialize_map(RootVisitor { f: &mut f })?;
+    deserializer.end()
+}
+
+/// Function to load an `AllPrintings.json` file into an `offline::Store`.
+///
+/// # Example
+/// ```rust,no_run
+/// use mtgsdk::import::mtgjson;
+/// use std::fs::File;
+///
+/// let store = mtgjson::load(File::open("AllPrintings.json").unwrap()).unwrap();
+/// assert_eq!(store.find_set("dom").unwrap().name, "Dominaria");
+/// ```
+///
+/// # Errors
+/// If the file cannot be read or is not a valid MTGJSON file, it will return a `Err(serde_json::Error)`.
+pub fn load<R: Read>(reader: R) -> serde_json::Result<Store> {
+    let mut store = Store::new();
+    for_each_set(reader, |set, cards| {
+        store.insert_set(set);
+        store.extend_cards(cards);
+    })?;
+    Ok(store)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ids::MultiverseId;
+    use crate::import::mtgjson;
+    use crate::sets::Booster;
+
+    const ALL_PRINTINGS: &str = r#"{
+        "meta": {"date": "2021-01-01", "version": "5.1.0"},
+        "data": {
+            "APC": {
+                "code": "APC",
+                "name": "Apocalypse",
+                "type": "expansion",
+                "block": "Invasion",
+                "releaseDate": "2001-06-04",
+                "booster": {"default": {"boosters": [
+                    {"contents": {"common": 11, "rare": 1, "uncommon": 3}, "weight": 1}
+                ]}},
+                "cards": [{
+                    "name": "Fire // Ice",
+                    "faceName": "Fire",
+                    "layout": "split",
+                    "manaValue": 4.0,
+                    "colors": ["R"],
+                    "colorIdentity": ["R", "U"],
+                    "type": "Instant",
+                    "types": ["Instant"],
+                    "rarity": "uncommon",
+                    "text": "Fire deals 2 damage divided as you choose among one or two targets.",
+                    "artist": "Franz Vohwinkel",
+                    "number": "128a",
+                    "uuid": "c0d7ad2d-0cd0-5396-8a1e-24d3c69a5a80",
+                    "identifiers": {"multiverseId": "27165"},
+                    "manaCost": "{1}{R}",
+                    "printings": ["APC", "MH2"],
+                    "foreignData": [{"language": "German", "name": "Feuer", "identifiers": {"multiverseId": "150011"}}],
+                    "legalities": {"legacy": "Legal", "modern": "Legal"},
+                    "rulings": [{"date": "2020-11-10", "text": "Fire targets only once."}]
+                }]
+            }
+        }
+    }"#;
+
+    #[test]
+    fn load_all_printings() {
+        let store = mtgjson::load(ALL_PRINTINGS.as_bytes()).unwrap();
+
+        let set = store.find_set("apc").unwrap();
+        assert_eq!(set.block.as_deref(), Some("Invasion"));
+        assert_eq!(set.booster.len(), 15);
+        assert_eq!(set.booster[0], Booster::Single("common".to_string()));
+
+        let card = store.find_card(27165).unwrap();
+        assert_eq!(card.name, "Fire");
+        assert_eq!(card.names.as_ref().unwrap().len(), 2);
+        assert_eq!(card.set_name, "Apocalypse");
+        assert_eq!(card.rarity, "Uncommon");
+        assert!(card.colors.as_ref().unwrap().contains("Red"));
+        assert!(card.color_identity.as_ref().unwrap().contains("Blue"));
+        assert!(card.legalities.iter().any(|l| l.format == "Modern"));
+        assert_eq!(
+            card.foreign_names[0].multiverseid,
+            Some(MultiverseId(150011))
+        );
+        assert_eq!(card.rulings.as_ref().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn invalid_file() {
+        assert!(mtgjson::load(r#"{"data": ["#.as_bytes()).is_err());
+    }
+}
@@ -0,0 +1,533 @@
+//! Read [Scryfall](https://scryfall.com/docs/api/bulk-data) bulk-data files (e.g.: "Default Cards").
+//!
+//! The file is a single JSON array and is parsed one card at a time, so it never needs to fit in memory.
+//! Scryfall and the API do not describe cards in the same way, so each conversion also fills a `Report`
+//! listing what could not be mapped into `cards::Card` (e.g.: `oracle_id`, `prices`).
+//!
+//! A few rules are worth knowing:
+//! - Cards with `card_faces` become one `Card` per face, as the API does. The first face keeps the Scryfall `id`,
+//!   the other ones get the face index appended (e.g.: `"<id>-1"`).
+//! - Set codes are uppercased to match the API (e.g.: "dom" becomes "DOM").
+//! - Scryfall has no `printings` list, so `Card::printings` only holds the card's own set.
+//! - `Set::release_date` is the earliest `released_at` among the set's cards. A card without a readable one is kept
+//!   without a `release_date`, and counted as `released_at` in `Report::unmapped`.
+#![allow(dead_code)]
+use super::rarity_name;
+use crate::cards::{self, format_name, Card, Legalities};
+use crate::date::{self, Date};
+use crate::ids::{CardId, MultiverseId, SetCode};
+use crate::offline::Store;
+use crate::sets::{self, Set};
+use serde::de::{Deserializer, SeqAccess, Visitor};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use std::io::{BufReader, Read};
+
+// Card supertypes, the other words before the dash being types
+const SUPERTYPES: [&str; 7] = [
+    "Basic",
+    "Legendary",
+    "Ongoing",
+    "Snow",
+    "World",
+    "Elite",
+    "Host",
+];
+
+// Fields that have a place in `Card` or `Set`, everything else is reported as unmapped
+const MAPPED: [&str; 28] = [
+    "object",
+    "id",
+    "name",
+    "layout",
+    "cmc",
+    "colors",
+    "color_identity",
+    "type_line",
+    "rarity",
+    "set",
+    "set_name",
+    "set_type",
+    "oracle_text",
+    "artist",
+    "collector_number",
+    "power",
+    "toughness",
+    "loyalty",
+    "multiverse_ids",
+    "mana_cost",
+    "image_uris",
+    "watermark",
+    "border_color",
+    "released_at",
+    "card_faces",
+    "legalities",
+    "digital",
+    "variation_of",
+];
+
+/// What could not be converted.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Report {
+    /// Scryfall fields with no equivalent in `Card` or `Set`, and how many cards had them (for `released_at`,
+    /// how many cards had none that could be read).
+    pub unmapped: BTreeMap<String, usize>,
+    /// Cards that could not be converted at all: their position in the file and the reason.
+    pub skipped: Vec<(usize, String)>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+struct ScryfallImageUris {
+    normal: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+struct ScryfallFace {
+    name: String,
+    mana_cost: Option<String>,
+    type_line: Option<String>,
+    oracle_text: Option<String>,
+    colors: Option<Vec<String>>,
+    power: Option<String>,
+    toughness: Option<String>,
+    loyalty: Option<String>,
+    artist: Option<String>,
+    watermark: Option<String>,
+    image_uris: Option<ScryfallImageUris>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+struct ScryfallCard {
+    id: String,
+    name: String,
+    layout: String,
+    cmc: f64,
+    colors: Option<Vec<String>>,
+    color_identity: Vec<String>,
+    type_line: String,
+    rarity: String,
+    set: String,
+    set_name: String,
+    set_type: String,
+    oracle_text: Option<String>,
+    artist: Option<String>,
+    collector_number: String,
+    power: Option<String>,
+    toughness: Option<String>,
+    loyalty: Option<String>,
+    multiverse_ids: Vec<u64>,
+    mana_cost: Option<String>,
+    image_uris: Option<ScryfallImageUris>,
+    watermark: Option<String>,
+    border_color: Option<String>,
+    #[serde(deserialize_with = "date::lenient")]
+    released_at: Option<Date>,
+    card_faces: Vec<ScryfallFace>,
+    legalities: BTreeMap<String, String>,
+    digital: Option<bool>,
+    variation_of: Option<String>,
+}
+
+fn color_names(symbols: &[String]) -> Option<HashSet<String>> {
+    if symbols.is_empty() {
+        return None;
+    }
+    Some(
+        symbols
+            .iter()
+            .map(|s| cards::color_name(s).unwrap_or(s).to_string())
+            .collect(),
+    )
+}
+
+fn non_empty(values: Vec<String>) -> Option<HashSet<String>> {
+    if values.is_empty() {
+        None
+    } else {
+        Some(values.into_iter().collect())
+    }
+}
+
+// Splits "Legendary Creature — Human Wizard" into supertypes, types and subtypes
+fn split_type_line(type_line: &str) -> (Vec<String>, Vec<String>, Vec<String>) {
+    let mut parts = type_line.splitn(2, '—');
+    let left = parts.next().unwrap_or_default();
+    let subtypes = parts
+        .next()
+        .map(|s| s.split_whitespace().map(String::from).collect())
+        .unwrap_or_default();
+    let (supertypes, types) = left
+        .split_whitespace()
+        .map(String::from)
+        .partition(|word| SUPERTYPES.contains(&word.as_str()));
+    (supertypes, types, subtypes)
+}
+
+fn convert_legalities(legalities: &BTreeMap<String, String>) -> Vec<Legalities> {
+    // The API only lists the formats where the card can be (or could have been) played
+    legalities
+        .iter()
+        .filter(|(_, legality)| legality.as_str() != "not_legal")
+        .map(|(format, legality)| Legalities {
+            format: format_name(format),
+            legality: format_name(legality),
+        })
+        .collect()
+}
+
+fn convert(card: ScryfallCard) -> (Set, Vec<Card>) {
+    let code = SetCode::from_raw(&card.set);
+    let set = Set {
+        code: code.clone(),
+        name: card.set_name.clone(),
+        type_field: card.set_type.clone(),
+        release_date: card.released_at,
+        online_only: card.digital,
+        ..Default::default()
+    };
+
+    // A card without faces is handled as a card with a single face
+    let faces = if card.card_faces.is_empty() {
+        vec![ScryfallFace {
+            name: card.name.clone(),
+            mana_cost: card.mana_cost.clone(),
+            type_line: Some(card.type_line.clone()),
+            oracle_text: card.oracle_text.clone(),
+            colors: card.colors.clone(),
+            power: card.power.clone(),
+            toughness: card.toughness.clone(),
+            loyalty: card.loyalty.clone(),
+            artist: card.artist.clone(),
+            watermark: card.watermark.clone(),
+            image_uris: None,
+        }]
+    } else {
+        card.card_faces.clone()
+    };
+    let names: Option<HashSet<String>> = if faces.len() > 1 {
+        Some(faces.iter().map(|f| f.name.clone()).collect())
+    } else {
+        None
+    };
+
+    let cards = faces
+        .into_iter()
+        .enumerate()
+        .map(|(i, face)| {
+            let type_line = face.type_line.unwrap_or_else(|| card.type_line.clone());
+            let (supertypes, types, subtypes) = split_type_line(&type_line);
+            let multiverseid = card
+                .multiverse_ids
+                .get(i)
+                .or_else(|| card.multiverse_ids.first())
+                .map(|id| MultiverseId(*id));
+            let image_uris = face.image_uris.as_ref().or(card.image_uris.as_ref());
+
+            Card {
+                name: face.name,
+                layout: card.layout.clone(),
+                cmc: card.cmc,
+                colors: color_names(
+                    face.colors
+                        .as_ref()
+                        .or(card.colors.as_ref())
+                        .unwrap_or(&Vec::new()),
+                ),
+                color_identity: color_names(&card.color_identity),
+                type_field: type_line,
+                supertypes: non_empty(supertypes),
+                types: types.into_iter().collect(),
+                subtypes: non_empty(subtypes),
+                rarity: rarity_name(&card.rarity),
+                set_field: code.clone(),
+                set_name: card.set_name.clone(),
+                text: face.oracle_text.unwrap_or_default(),
+                artist: face
+                    .artist
+                    .or_else(|| card.artist.clone())
+                    .unwrap_or_default(),
+                number: card.collector_number.clone(),
+                power: face.power,
+                toughness: face.toughness,
+                loyalty: face.loyalty,
+                id: if i == 0 {
+                    CardId::from_raw(card.id.clone())
+                } else {
+                    CardId::from_raw(format!("{}-{}", card.id, i))
+                },
+                multiverseid,
+                names: names.clone(),
+                mana_cost: face.mana_cost.filter(|cost| !cost.is_empty()),
+                variations: card.variation_of.clone().map(|id| vec![id]),
+                image_url: image_uris.and_then(|uris| uris.normal.clone()),
+                watermark: face.watermark.or_else(|| card.watermark.clone()),
+                border: card.border_color.clone(),
+                release_date: card.released_at,
+                rulings: None,
+                foreign_names: Vec::new(),
+                printings: std::iter::once(code.clone()).collect(),
+                original_text: None,
+                original_type: None,
+                legalities: convert_legalities(&card.legalities),
+            }
+        })
+        .collect();
+
+    (set, cards)
+}
+
+// Converts a single element of the bulk array, noting what was left behind
+fn convert_value(index: usize, value: Value, report: &mut Report) -> Option<(Set, Vec<Card>)> {
+    let object: Map<String, Value> = match value {
+        Value::Object(object) => object,
+        _ => {
+            report
+                .skipped
+                .push((index, String::from("not a JSON object")));
+            return None;
+        }
+    };
+
+    for key in object.keys() {
+        if !MAPPED.contains(&key.as_str()) {
+            *report.unmapped.entry(key.clone()).or_insert(0) += 1;
+        }
+    }
+
+    match serde_json::from_value::<ScryfallCard>(Value::Object(object)) {
+        Ok(card) if card.name.is_empty() => {
+            report.skipped.push((index, String::from("missing name")));
+            None
+        }
+        Ok(card) => {
+            if card.released_at.is_none() {
+                *report
+                    .unmapped
+                    .entry(String::from("released_at"))
+                    .or_insert(0) += 1;
+            }
+            Some(convert(card))
+        }
+        Err(e) => {
+            report.skipped.push((index, e.to_string()));
+            None
+        }
+    }
+}
+
+struct CardsVisitor<'f, F> {
+    f: &'f mut F,
+}
+
+impl<'de, 'f, F> Visitor<'de> for CardsVisitor<'f, F>
+where
+    F: FnMut(Set, Vec<Card>),
+{
+    type Value = Report;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a Scryfall bulk-data array of cards")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Report, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut report = Report::default();
+        let mut index = 0;
+        while let Some(value) = seq.next_element::<Value>()? {
+            if let Some((set, cards)) = convert_value(index, value, &mut report) {
+                (self.f)(set, cards);
+            }
+            index += 1;
+        }
+        Ok(report)
+    }
+}
+
+/// Function to read a Scryfall bulk-data file one card at a time.
+///
+/// `f` is called with each card's set and its faces as soon as they are read.
+/// Cards that cannot be converted are skipped and listed in the returned `Report`.
+///
+/// # Example
+/// ```rust
+/// use mtgsdk::import::scryfall;
+///
+/// let json = r#"[{"id": "e3285e6b-3e79-4d7c-bf96-d920f973b122", "oracle_id": "4457ed35-7c10-48c8-9776-456485fdf070",
+///     "name": "Lightning Bolt", "layout": "normal", "type_line": "Instant", "set": "m10", "rarity": "common"}]"#;
+/// let mut names = Vec::new();
+/// let report = scryfall::for_each_card(json.as_bytes(), |_set, cards| {
+///     names.extend(cards.into_iter().map(|card| card.name))
+/// }).unwrap();
+/// assert_eq!(names, vec!["Lightning Bolt"]);
+/// assert_eq!(report.unmapped.get("oracle_id"), Some(&1));
+/// ```
+///
+/// # Errors
+/// If the file cannot be read or is not a JSON array, it will return a `Err(serde_json::Error)`.
+pub fn for_each_card<R, F>(reader: R, mut f: F) -> serde_json::Result<Report>
+where
+    R: Read,
+    F: FnMut(Set, Vec<Card>),
+{
+    let mut deserializer = serde_json::Deserializer::from_reader(BufReader::new(reader));
+    let report = deserializer.deserialize_seq(CardsVisitor { f: &mut f })?;
+    deserializer.end()?;
+    Ok(report)
+}
+
+/// Function to load a Scryfall bulk-data file into an `offline::Store`.
+///
+/// # Example
+/// ```rust,no_run
+/// use mtgsdk::import::scryfall;
+/// use std::fs::File;
+///
+/// let (store, report) = scryfall::load(File::open("default-cards.json").unwrap()).unwrap();
+/// assert!(store.find_card(386616).is_some());
+/// assert!(report.skipped.is_empty());
+/// ```
+///
+/// # Errors
+/// If the file cannot be read or is not a JSON array, it will return a `Err(serde_json::Error)`.
+pub fn load<R: Read>(reader: R) -> serde_json::Result<(Store, Report)> {
+    let mut store = Store::new();
+    let mut sets: HashMap<SetCode, Set> = HashMap::new();
+
+    let report = for_each_card(reader, |set, cards| {
+        let known = sets.entry(set.code.clone()).or_insert_with(|| set.clone());
+        if let Some(date) = set.release_date {
+            if known.release_date.is_none_or(|known| date < known) {
+                known.release_date = Some(date);
+            }
+        }
+        store.extend_cards(cards);
+    })?;
+
+    let mut sets: Vec<Set> = sets.into_values().collect();
+    sets.sort_by(sets::by_release);
+    for set in sets {
+        store.insert_set(set);
+    }
+    Ok((store, report))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::import::scryfall;
+
+    const DEFAULT_CARDS: &str = r#"[
+        {
+            "object": "card",
+            "id": "a0dc3a09-1c38-4a62-a1c7-3b5bc4d5b5b1",
+            "oracle_id": "ecca3f29-8bd4-4a83-9e34-bb4b8d7bd2a2",
+            "multiverse_ids": [386616],
+            "name": "Narset, Enlightened Master",
+            "released_at": "2014-09-26",
+            "layout": "normal",
+            "image_uris": {"small": "https://cards.scryfall.io/small/narset.jpg", "normal": "https://cards.scryfall.io/normal/narset.jpg"},
+            "mana_cost": "{3}{U}{R}{W}",
+            "cmc": 6.0,
+            "type_line": "Legendary Creature — Human Monk",
+            "power": "3",
+            "toughness": "2",
+            "colors": ["R", "U", "W"],
+            "color_identity": ["R", "U", "W"],
+            "legalities": {"standard": "not_legal", "modern": "legal", "commander": "legal"},
+            "set": "ktk",
+            "set_name": "Khans of Tarkir",
+            "set_type": "expansion",
+            "collector_number": "190",
+            "rarity": "mythic",
+            "artist": "Magali Villeneuve",
+            "prices": {"usd": "1.00"}
+        },
+        {
+            "object": "card",
+            "id": "c0e2e5ef-3f8e-4b5c-98d5-2b4a1c5e8e7d",
+            "name": "Delver of Secrets // Insectile Aberration",
+            "released_at": "2011-09-30",
+            "layout": "transform",
+            "cmc": 1.0,
+            "type_line": "Creature — Human Wizard // Creature — Human Insect",
+            "color_identity": ["U"],
+            "set": "isd",
+            "set_name": "Innistrad",
+            "set_type": "expansion",
+            "collector_number": "51",
+            "rarity": "common",
+            "card_faces": [
+                {"name": "Delver of Secrets", "mana_cost": "{U}", "type_line": "Creature — Human Wizard", "colors": ["U"], "power": "1", "toughness": "1"},
+                {"name": "Insectile Aberration", "mana_cost": "", "type_line": "Creature — Human Insect", "colors": ["U"], "power": "3", "toughness": "2"}
+            ]
+        },
+        "not a card"
+    ]"#;
+
+    #[test]
+    fn load_default_cards() {
+        let (store, report) = scryfall::load(DEFAULT_CARDS.as_bytes()).unwrap();
+
+        let narset = store.find_card(386616).unwrap();
+        assert_eq!(narset.set_field, "KTK");
+        assert_eq!(narset.rarity, "Mythic Rare");
+        assert!(narset.supertypes.as_ref().unwrap().contains("Legendary"));
+        assert!(narset.subtypes.as_ref().unwrap().contains("Monk"));
+        assert!(narset.colors.as_ref().unwrap().contains("White"));
+        assert_eq!(narset.legalities.len(), 2);
+        assert_eq!(
+            narset.image_url.as_deref(),
+            Some("https://cards.scryfall.io/normal/narset.jpg")
+        );
+
+        let faces: Vec<_> = store.cards_in_set("isd").collect();
+        assert_eq!(faces.len(), 2);
+        assert_eq!(faces[1].name, "Insectile Aberration");
+        assert_eq!(faces[1].id, "c0e2e5ef-3f8e-4b5c-98d5-2b4a1c5e8e7d-1");
+        assert_eq!(faces[1].mana_cost, None);
+        assert!(faces[1]
+            .names
+            .as_ref()
+            .unwrap()
+            .contains("Delver of Secrets"));
+
+        assert_eq!(
+            store
+                .find_set("ISD")
+                .unwrap()
+                .release_date
+                .unwrap()
+                .to_string(),
+            "2011-09-30"
+        );
+        assert_eq!(report.unmapped.get("oracle_id"), Some(&1));
+        assert_eq!(report.unmapped.get("prices"), Some(&1));
+        assert_eq!(report.skipped.len(), 1);
+    }
+
+    #[test]
+    fn missing_release_dates() {
+        let json = r#"[
+            {"id": "1", "name": "Brainstorm", "set": "isd", "set_name": "Innistrad", "collector_number": "1"},
+            {"id": "2", "name": "Ponder", "set": "isd", "released_at": "2011-09-30", "collector_number": "2"},
+            {"id": "3", "name": "Preordain", "set": "isd", "released_at": "soon", "collector_number": "3"}
+        ]"#;
+        let (store, report) = scryfall::load(json.as_bytes()).unwrap();
+
+        let isd = store.find_set("ISD").unwrap();
+        assert_eq!(isd.release_date.unwrap().to_string(), "2011-09-30");
+        let cards: Vec<_> = store.cards_in_set("isd").collect();
+        assert_eq!(cards.len(), 3);
+        assert!(cards
+            .iter()
+            .all(|card| card.release_date.is_some() == (card.name == "Ponder")));
+        assert_eq!(report.unmapped.get("released_at"), Some(&2));
+        assert!(report.skipped.is_empty());
+    }
+}
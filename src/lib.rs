@@ -8,17 +8,71 @@
 //! # Using the `all()` function
 //! Returns the all the information available through a specific endpoint.
 //! 
+//! # HTTP client
+//! Every call has a variant taking a `client::Client` (e.g.: `cards::find_with()`, `cards::filter().client()`).
+//! The client sends the requests through an `HttpTransport`, which defaults to `reqwest` (the `reqwest` feature, enabled by default)
+//! and can be replaced to use another HTTP stack or async runtime.
+//!
+//! # Catalog
+//! `catalog` keeps the formats, types, subtypes and supertypes for a while, and checks the values of `cards::filter()`
+//! against them (e.g.: catching "goblinn" before it returns no cards).
+//!
+//! # Query language
+//! `query` parses searches in the Scryfall syntax (e.g.: `t:legendary c>=rw cmc<=4`), sending to the API what it can filter
+//! and checking the rest on the cards returned.
+//! `select` filters and sorts the cards already fetched (e.g.: by color, then cmc, then name).
+//! `stats` reads power, toughness and loyalty (e.g.: "1+*") into values that can be compared,
+//! and `type_line` reads type lines and checks them against the types catalogs.
+//!
+//! # Formats over time
+//! `date` reads the release dates of sets and cards, and `sets` orders sets chronologically (e.g.: `sets::newest_on()`).
+//! `printings` gets the sets and cards behind `Card::printings` and `Card::variations`.
+//! `set_index` groups sets into blocks and families, and resolves their old codes.
+//! `rotation` works out which sets were legal in Standard on a given date, and the card pool they made.
+//!
+//! # Blocking API
+//! With the `blocking` feature, `blocking` offers the same calls without async (e.g.: `blocking::cards::find()`).
+//!
+//! # Offline data
+//! `import` converts third-party dumps (e.g.: MTGJSON, Scryfall) into the same structures, which can be queried through `offline::Store`.
+//! `export` writes them to other formats (e.g.: Cockatrice, CSV, JSON Lines).
+//! `search` looks the cards up locally (e.g.: misspelled names).
+//! `oracle` collapses the printings of a card (one `Card` each) into a single `OracleCard`.
+//!
 //! # Example
 //! Check the **Modules** below for examples of each function's usage.
-mod query_builder;
+#[cfg(feature = "blocking")]
+pub mod blocking;
 pub mod cards;
+pub mod catalog;
+pub mod client;
+pub mod date;
+pub mod export;
+#[cfg(test)]
+mod fixtures;
 pub mod formats;
+pub mod ids;
+pub mod import;
+pub mod offline;
+pub mod oracle;
+pub mod printings;
+pub mod query;
+mod query_builder;
+pub mod rotation;
+pub mod search;
+pub mod select;
+pub mod set_index;
 pub mod sets;
+pub mod stats;
 pub mod subtypes;
 pub mod supertypes;
+pub mod type_line;
 pub mod types;
 
+pub use http::StatusCode;
+
 #[cfg(test)]
+#[allow(clippy::len_zero, clippy::get_first)]
 mod tests {
     use crate::*;
 
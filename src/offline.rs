@@ -0,0 +1,104 @@
+//! Query cards and sets without calling the API.
+//!
+//! A `Store` holds `Card`s and `Set`s loaded from a local source (e.g.: an MTGJSON dump, see `import::mtgjson`)
+//! and answers the same questions as `cards::find()`, `sets::find()` and the filters, but without any network.
+#![allow(dead_code)]
+use crate::cards::{Card, CardKey};
+use crate::sets::Set;
+
+/// In-memory collection of cards and sets.
+///
+/// # Example
+/// ```rust
+/// use mtgsdk::offline::Store;
+/// use mtgsdk::sets::Set;
+///
+/// let mut store = Store::new();
+/// store.insert_set(Set { code: "DOM".parse().unwrap(), name: "Dominaria".to_string(), ..Default::default() });
+/// assert_eq!(store.find_set("dom").unwrap().name, "Dominaria");
+/// ```
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct Store {
+    sets: Vec<Set>,
+    cards: Vec<Card>,
+}
+
+impl Store {
+    pub fn new() -> Self {
+        Store::default()
+    }
+
+    /// Adds a set, replacing any set already stored with the same code.
+    pub fn insert_set(&mut self, set: Set) {
+        match self
+            .sets
+            .iter_mut()
+            .find(|s| s.code.eq_ignore_ascii_case(&set.code))
+        {
+            Some(s) => *s = set,
+            None => self.sets.push(set),
+        }
+    }
+
+    pub fn extend_cards<I: IntoIterator<Item = Card>>(&mut self, cards: I) {
+        self.cards.extend(cards);
+    }
+
+    pub fn sets(&self) -> &[Set] {
+        &self.sets
+    }
+
+    pub fn cards(&self) -> &[Card] {
+        &self.cards
+    }
+
+    /// Same as `cards::find()`: looks a card up by its multiverse id or by its unique `Card::id`.
+    pub fn find_card<K: Into<CardKey>>(&self, id: K) -> Option<&Card> {
+        let key = id.into();
+        self.cards.iter().find(|card| key.matches(card))
+    }
+
+    /// Looks a card up by its unique `Card::id`.
+    pub fn find_card_by_id(&self, id: &str) -> Option<&Card> {
+        self.cards.iter().find(|card| card.id == id)
+    }
+
+    /// Same as `sets::find()`: looks a set up by its code (case insensitive).
+    pub fn find_set(&self, code: &str) -> Option<&Set> {
+        self.sets
+            .iter()
+            .find(|set| set.code.eq_ignore_ascii_case(code))
+    }
+
+    /// Returns every card printed in the set `code`.
+    pub fn cards_in_set<'a>(&'a self, code: &'a str) -> impl Iterator<Item = &'a Card> + 'a {
+        self.cards
+            .iter()
+            .filter(move |card| card.set_field.eq_ignore_ascii_case(code))
+    }
+
+    /// Returns every card matching `predicate`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use mtgsdk::offline::Store;
+    ///
+    /// let store = Store::new();
+    /// let karns: Vec<_> = store.filter_cards(|card| card.name.contains("Karn")).collect();
+    /// assert!(karns.is_empty());
+    /// ```
+    pub fn filter_cards<'a, P>(&'a self, predicate: P) -> impl Iterator<Item = &'a Card> + 'a
+    where
+        P: Fn(&Card) -> bool + 'a,
+    {
+        self.cards.iter().filter(move |card| predicate(card))
+    }
+
+    /// Returns every set matching `predicate`.
+    pub fn filter_sets<'a, P>(&'a self, predicate: P) -> impl Iterator<Item = &'a Set> + 'a
+    where
+        P: Fn(&Set) -> bool + 'a,
+    {
+        self.sets.iter().filter(move |set| predicate(set))
+    }
+}
@@ -0,0 +1,355 @@
+//! Oracle cards: the printings of a card collapsed into one logical card.
+//!
+//! The API returns one `Card` per printing (e.g.: "Lightning Bolt" in each set it was printed in). An `OracleCard`
+//! holds what the printings share once (the Oracle text, mana cost, types, legalities, rulings, ...) and a `Printing`
+//! for each of them (set, number, rarity, artist, image, multiverse id).
+//!
+//! Printings are grouped by name, and by layout for the cards with many faces (e.g.: "Fire" of the split card
+//! Fire // Ice is not grouped with an "Adventure" card of the same name).
+//! Deck tools usually want the oracle cards, collection tools the printings.
+//!
+//! # Example
+//! ```rust
+//! use mtgsdk::cards;
+//! use mtgsdk::oracle;
+//! async {
+//!     let stream = cards::filter().name("Lightning Bolt").stream();
+//!     let oracle_cards = oracle::from_stream(stream).await.unwrap();
+//!     let bolt = oracle_cards.iter().find(|card| card.name == "Lightning Bolt").unwrap();
+//!     assert!(bolt.printings.len() > 10);
+//! };
+//! ```
+#![allow(dead_code)]
+use crate::cards::{Card, Legalities, Rulings};
+use crate::date::Date;
+use crate::ids::{CardId, MultiverseId, SetCode};
+use futures::stream::{Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::borrow::Borrow;
+use std::collections::{BTreeMap, HashSet};
+
+/// A card and all its printings.
+///
+/// The Oracle fields are taken from the first printing added, those it lacks from the next ones.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OracleCard {
+    pub name: String,
+    pub layout: String,
+    pub names: Option<HashSet<String>>,
+    pub mana_cost: Option<String>,
+    pub cmc: f64,
+    pub colors: Option<HashSet<String>>,
+    pub color_identity: Option<HashSet<String>>,
+    #[serde(rename = "type")]
+    pub type_field: String,
+    pub supertypes: Option<HashSet<String>>,
+    pub types: HashSet<String>,
+    pub subtypes: Option<HashSet<String>>,
+    pub text: String,
+    pub power: Option<String>,
+    pub toughness: Option<String>,
+    pub loyalty: Option<String>,
+    pub legalities: Vec<Legalities>,
+    pub rulings: Vec<Rulings>,
+    /// Ordered by release date (the ones without a date last), then set and number.
+    pub printings: Vec<Printing>,
+}
+
+/// What differs between the printings of a card.
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Printing {
+    pub id: CardId,
+    pub set: SetCode,
+    pub set_name: String,
+    pub number: String,
+    pub rarity: String,
+    pub artist: String,
+    pub image_url: Option<String>,
+    pub multiverseid: Option<MultiverseId>,
+    pub release_date: Option<Date>,
+}
+
+impl Printing {
+    pub fn of(card: &Card) -> Printing {
+        Printing {
+            id: card.id.clone(),
+            set: card.set_field.clone(),
+            set_name: card.set_name.clone(),
+            number: card.number.clone(),
+            rarity: card.rarity.clone(),
+            artist: card.artist.clone(),
+            image_url: card.image_url.clone(),
+            multiverseid: card.multiverseid,
+            release_date: card.release_date,
+        }
+    }
+}
+
+impl OracleCard {
+    fn of(card: &Card) -> OracleCard {
+        OracleCard {
+            name: card.name.clone(),
+            layout: card.layout.clone(),
+            names: card.names.clone(),
+            mana_cost: card.mana_cost.clone(),
+            cmc: card.cmc,
+            colors: card.colors.clone(),
+            color_identity: card.color_identity.clone(),
+            type_field: card.type_field.clone(),
+            supertypes: card.supertypes.clone(),
+            types: card.types.clone(),
+            subtypes: card.subtypes.clone(),
+            text: card.text.clone(),
+            power: card.power.clone(),
+            toughness: card.toughness.clone(),
+            loyalty: card.loyalty.clone(),
+            legalities: card.legalities.clone(),
+            rulings: card.rulings.clone().unwrap_or_default(),
+            printings: Vec::new(),
+        }
+    }
+
+    // Fill what the first printings lacked
+    fn complete(&mut self, card: &Card) {
+        fn fill<T: Clone>(field: &mut Option<T>, value: &Option<T>) {
+            if field.is_none() {
+                field.clone_from(value);
+            }
+        }
+        fill(&mut self.names, &card.names);
+        fill(&mut self.mana_cost, &card.mana_cost);
+        fill(&mut self.colors, &card.colors);
+        fill(&mut self.color_identity, &card.color_identity);
+        fill(&mut self.supertypes, &card.supertypes);
+        fill(&mut self.subtypes, &card.subtypes);
+        fill(&mut self.power, &card.power);
+        fill(&mut self.toughness, &card.toughness);
+        fill(&mut self.loyalty, &card.loyalty);
+        if self.text.is_empty() {
+            self.text.clone_from(&card.text);
+        }
+        if self.legalities.is_empty() {
+            self.legalities.clone_from(&card.legalities);
+        }
+        if self.rulings.is_empty() {
+            self.rulings = card.rulings.clone().unwrap_or_default();
+        }
+    }
+
+    /// The printing with the given set code (case insensitive), if any.
+    pub fn printing_in(&self, set: &str) -> Option<&Printing> {
+        self.printings
+            .iter()
+            .find(|printing| printing.set.eq_ignore_ascii_case(set))
+    }
+
+    /// The printing released first (`None` if no printing has a release date).
+    pub fn first_printing(&self) -> Option<&Printing> {
+        self.printings
+            .first()
+            .filter(|printing| printing.release_date.is_some())
+    }
+}
+
+/// Groups printings into oracle cards, one card at a time.
+///
+/// # Example
+/// ```rust
+/// use mtgsdk::cards::Card;
+/// use mtgsdk::ids::{CardId, SetCode};
+/// use mtgsdk::oracle::OracleBuilder;
+///
+/// let mut builder = OracleBuilder::new();
+/// for (set, id) in [("LEA", "d573ef03"), ("M10", "b8ac1ad2")] {
+///     builder.add(&Card {
+///         id: CardId::parse(&format!("{}-0000-0000-0000-000000000000", id)).unwrap(),
+///         name: "Lightning Bolt".to_string(),
+///         set_field: SetCode::parse(set).unwrap(),
+///         ..Default::default()
+///     });
+/// }
+/// let oracle_cards = builder.build();
+/// assert_eq!(oracle_cards.len(), 1);
+/// assert_eq!(oracle_cards[0].printings.len(), 2);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct OracleBuilder {
+    // By name and, for cards with many faces, layout (both in lowercase)
+    cards: BTreeMap<(String, String), OracleCard>,
+}
+
+impl OracleBuilder {
+    pub fn new() -> Self {
+        OracleBuilder::default()
+    }
+
+    /// Adds a printing. The same printing (by `Card::id`) is only added once.
+    pub fn add(&mut self, card: &Card) {
+        let layout = if card.names.is_some() {
+            card.layout.to_lowercase()
+        } else {
+            String::new()
+        };
+        let oracle = self
+            .cards
+            .entry((card.name.to_lowercase(), layout))
+            .or_insert_with(|| OracleCard::of(card));
+        if oracle
+            .printings
+            .iter()
+            .any(|printing| printing.id == card.id)
+        {
+            return;
+        }
+        oracle.complete(card);
+        oracle.printings.push(Printing::of(card));
+    }
+
+    pub fn len(&self) -> usize {
+        self.cards.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cards.is_empty()
+    }
+
+    /// The oracle cards, ordered by name (then layout).
+    pub fn build(self) -> Vec<OracleCard> {
+        let mut cards: Vec<OracleCard> = self.cards.into_values().collect();
+        for card in cards.iter_mut() {
+            card.printings.sort_by(|a, b| {
+                let date = |p: &Printing| (p.release_date.is_none(), p.release_date);
+                date(a)
+                    .cmp(&date(b))
+                    .then_with(|| a.set.cmp(&b.set))
+                    .then_with(|| a.number.cmp(&b.number))
+            });
+        }
+        cards
+    }
+}
+
+impl<C: Borrow<Card>> Extend<C> for OracleBuilder {
+    fn extend<I: IntoIterator<Item = C>>(&mut self, cards: I) {
+        for card in cards {
+            self.add(card.borrow());
+        }
+    }
+}
+
+/// Function to group printings into oracle cards, ordered by name.
+pub fn from_cards<I, C>(cards: I) -> Vec<OracleCard>
+where
+    I: IntoIterator<Item = C>,
+    C: Borrow<Card>,
+{
+    let mut builder = OracleBuilder::new();
+    builder.extend(cards);
+    builder.build()
+}
+
+/// Function to group a stream of printings (e.g.: `cards::Where::stream()`) into oracle cards, ordered by name.
+///
+/// # Errors
+/// If the stream yields an error, it will return it.
+pub async fn from_stream<S, E>(cards: S) -> Result<Vec<OracleCard>, E>
+where
+    S: Stream<Item = Result<Card, E>>,
+{
+    let mut builder = OracleBuilder::new();
+    let mut cards = Box::pin(cards);
+    while let Some(card) = cards.next().await {
+        builder.add(&card?);
+    }
+    Ok(builder.build())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cards::{Card, Legalities, Rulings};
+    use crate::fixtures::{card_id, printing, set_code, strings};
+    use crate::oracle::{self, OracleCard};
+    use futures::executor::block_on;
+    use futures::stream;
+
+    fn card(id: u32, name: &str, set: &str, release_date: Option<&str>) -> Card {
+        Card {
+            release_date: release_date.map(|date| date.parse().unwrap()),
+            ..printing(id, name, set)
+        }
+    }
+
+    fn sets(card: &OracleCard) -> Vec<&str> {
+        card.printings
+            .iter()
+            .map(|printing| printing.set.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn group_printings() {
+        let mut m10 = card(2, "Lightning Bolt", "M10", Some("2009-07-17"));
+        m10.text = "Lightning Bolt deals 3 damage to any target.".to_string();
+        m10.legalities = vec![Legalities {
+            format: "Modern".to_string(),
+            legality: "Legal".to_string(),
+        }];
+        let mut lea = card(1, "Lightning Bolt", "LEA", Some("1993-08-05"));
+        lea.rulings = Some(vec![Rulings {
+            date: "2004-10-04".to_string(),
+            text: "It can target a player.".to_string(),
+        }]);
+        let promo = card(3, "lightning bolt", "PRM", None);
+
+        let mut fire = card(4, "Fire", "APC", Some("2001-06-04"));
+        fire.layout = "split".to_string();
+        fire.names = Some(strings(&["Fire", "Ice"]));
+        let mut fire_mh2 = fire.clone();
+        fire_mh2.id = card_id(5);
+        fire_mh2.set_field = set_code("MH2");
+        let mut other_fire = card(6, "Fire", "UST", Some("2017-12-08"));
+        other_fire.layout = "adventure".to_string();
+        other_fire.names = Some(strings(&["Fire", "Water"]));
+
+        let cards = vec![
+            m10.clone(),
+            lea,
+            promo,
+            m10,
+            fire.clone(),
+            fire_mh2,
+            other_fire,
+        ];
+        let oracle_cards = oracle::from_cards(&cards);
+        let names: Vec<(&str, &str)> = oracle_cards
+            .iter()
+            .map(|card| (card.name.as_str(), card.layout.as_str()))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("Fire", "adventure"),
+                ("Fire", "split"),
+                ("Lightning Bolt", "normal")
+            ]
+        );
+
+        let bolt = &oracle_cards[2];
+        assert_eq!(sets(bolt), vec!["LEA", "M10", "PRM"]);
+        assert_eq!(bolt.text, "Lightning Bolt deals 3 damage to any target.");
+        assert_eq!(bolt.legalities.len(), 1);
+        assert_eq!(bolt.rulings.len(), 1);
+        assert_eq!(bolt.first_printing().unwrap().set, "LEA");
+        assert_eq!(bolt.printing_in("m10").unwrap().number, "2");
+
+        assert_eq!(sets(&oracle_cards[1]), vec!["APC", "MH2"]);
+
+        let stream = stream::iter(cards.into_iter().map(Ok::<Card, ()>));
+        let from_stream = block_on(oracle::from_stream(stream)).unwrap();
+        assert_eq!(from_stream, oracle_cards);
+        let failing = stream::iter(vec![Ok(fire), Err(())]);
+        assert!(block_on(oracle::from_stream(failing)).is_err());
+    }
+}
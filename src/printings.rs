@@ -0,0 +1,434 @@
+//! The printings and variations of a card, as full sets and cards.
+//!
+//! `Card::printings` only has set codes, and `Card::variations` only card ids. A `Printings` gets the sets and cards
+//! they stand for: every set in a single call, all the printings of a card in a single search (by its exact name),
+//! and the variations concurrently. It keeps what it fetched, so the sets and cards already known are not fetched again
+//! (e.g.: the sets shared by the printings of many cards): hold one for all the cards to look at.
+//!
+//! `Card::fetch_printings()` and `Card::fetch_variations()` do the same through a `Printings`, and
+//! `Card::fetch_printings_with()` and `Card::fetch_variations_with()` through a new one for the given `Client`.
+//!
+//! # Example
+//! ```rust
+//! use mtgsdk::cards;
+//! use mtgsdk::printings::Printings;
+//! async {
+//!     let printings = Printings::new();
+//!     let card = cards::find_by_name("Opt", Some("XLN")).await.unwrap();
+//!     let found = card.fetch_printings(&printings).await.unwrap();
+//!     assert_eq!(found[0].0.name, "Invasion");
+//!     assert!(found.iter().all(|(set, card)| card.set_field == set.code));
+//! };
+//! ```
+#![allow(dead_code)]
+use crate::cards::{self, Card, CardKey};
+use crate::client::Client;
+use crate::ids::{CardId, SetCode};
+use crate::sets::{self, Set};
+use futures::lock::Mutex;
+use http::StatusCode;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+// Requests sent at the same time
+const IN_FLIGHT: usize = 4;
+
+/// Fetches printings and variations, keeping the sets and cards it fetched.
+///
+/// Cloning it is cheap: the clones share what is kept.
+#[derive(Clone)]
+pub struct Printings {
+    client: Client,
+    known: Arc<Mutex<Known>>,
+}
+
+#[derive(Default)]
+struct Known {
+    // Every set, once loaded
+    sets: Option<HashMap<SetCode, Set>>,
+    // Every printing of a card, by its name in lowercase
+    printings: HashMap<String, Vec<Card>>,
+    cards: HashMap<CardId, Card>,
+}
+
+impl Default for Printings {
+    fn default() -> Self {
+        Printings::new()
+    }
+}
+
+impl Printings {
+    pub fn new() -> Self {
+        Printings::with_client(&Client::default())
+    }
+
+    /// Fetches with the given `Client` (see `client`).
+    pub fn with_client(client: &Client) -> Self {
+        Printings {
+            client: client.clone(),
+            known: Arc::new(Mutex::new(Known::default())),
+        }
+    }
+
+    /// Function to get each printing of the card (see `Card::printings`) with its set, ordered by release date.
+    ///
+    /// The printings the API does not return (e.g.: a set not found) are left out.
+    /// The first call loads every set (see `sets::all()`).
+    /// When a set has many printings of the card (e.g.: basic lands), `card` is used if it is from that set,
+    /// the first one returned otherwise.
+    ///
+    /// # Errors
+    /// If a call fails, it will return a `Err(StatusCode)`. When a page of the printings fails, the printings of
+    /// the other pages are kept, and the call only fails if a set of the card has none of them.
+    /// To see the possible return values, check the [API docs](https://docs.magicthegathering.io/#documentationerrors).
+    pub async fn printings(&self, card: &Card) -> Result<Vec<(Set, Card)>, StatusCode> {
+        let name = card.name.to_lowercase();
+        let (sets_known, known_printings) = {
+            let known = self.known.lock().await;
+            (known.sets.is_some(), known.printings.get(&name).cloned())
+        };
+
+        let sets = if sets_known {
+            None
+        } else {
+            Some(sets::all_with(&self.client).await?)
+        };
+        let (printings, failed) = match known_printings {
+            Some(printings) => (printings, None),
+            None => self.fetch_cards(&card.name).await,
+        };
+
+        let mut known = self.known.lock().await;
+        if let Some(sets) = sets {
+            known.sets = Some(
+                sets.into_iter()
+                    .map(|set| (set.code.clone(), set))
+                    .collect(),
+            );
+        }
+        for printing in printings.iter() {
+            known.cards.insert(printing.id.clone(), printing.clone());
+        }
+        // Incomplete printings are fetched again by the next call
+        if failed.is_none() {
+            known.printings.insert(name, printings.clone());
+        }
+
+        let mut found: Vec<(Set, Card)> = Vec::new();
+        for code in card.printings.iter() {
+            let set = match known.sets.as_ref().and_then(|sets| sets.get(code)) {
+                Some(set) => set,
+                None => continue,
+            };
+            let in_set: Vec<&Card> = printings
+                .iter()
+                .filter(|printing| printing.set_field == *code)
+                .collect();
+            let printing = in_set
+                .iter()
+                .find(|printing| printing.id == card.id)
+                .or_else(|| in_set.first());
+            match (printing, failed) {
+                (Some(printing), _) => found.push((set.clone(), (*printing).clone())),
+                (None, Some(e)) => return Err(e),
+                (None, None) => {}
+            }
+        }
+        found.sort_by(|(a, _), (b, _)| sets::by_release(a, b));
+        Ok(found)
+    }
+
+    /// Function to get the variations of the card (see `Card::variations`), in the same order.
+    ///
+    /// The variations the API does not return are left out.
+    ///
+    /// # Errors
+    /// If a call fails, it will return a `Err(StatusCode)` (the variations fetched by the other calls are kept for
+    /// the next one).
+    /// To see the possible return values, check the [API docs](https://docs.magicthegathering.io/#documentationerrors).
+    pub async fn variations(&self, card: &Card) -> Result<Vec<Card>, StatusCode> {
+        let ids: Vec<CardId> = card
+            .variations
+            .iter()
+            .flatten()
+            .filter_map(|id| CardId::parse(id).ok())
+            .collect();
+        let missing: Vec<CardKey> = {
+            let known = self.known.lock().await;
+            ids.iter()
+                .filter(|id| !known.cards.contains_key(*id))
+                .map(|id| CardKey::from(id.clone()))
+                .collect()
+        };
+
+        let mut fetched = Vec::new();
+        let mut failed = None;
+        if !missing.is_empty() {
+            for (_, found) in cards::find_many_with(&self.client, missing).await {
+                match found {
+                    Ok(card) => fetched.push(card),
+                    Err(StatusCode::NOT_FOUND) => {}
+                    Err(e) => failed = failed.or(Some(e)),
+                }
+            }
+        }
+
+        let mut known = self.known.lock().await;
+        for card in fetched {
+            known.cards.insert(card.id.clone(), card);
+        }
+        if let Some(e) = failed {
+            return Err(e);
+        }
+        Ok(ids
+            .iter()
+            .filter_map(|id| known.cards.get(id).cloned())
+            .collect())
+    }
+
+    /// Drops the sets and cards kept, so that they are fetched again.
+    pub async fn clear(&self) {
+        *self.known.lock().await = Known::default();
+    }
+
+    // Printings of the pages fetched, and the error of the first page that failed
+    async fn fetch_cards(&self, name: &str) -> (Vec<Card>, Option<StatusCode>) {
+        let (cards, errors) = cards::by_name(name, None)
+            .client(&self.client)
+            .page_size(100)
+            .fetch_pages_concurrent(IN_FLIGHT)
+            .await;
+        let printings = cards
+            .into_iter()
+            .filter(|card| cards::is_printing(card, name, None))
+            .collect();
+        (printings, errors.first().map(|(_, e)| *e))
+    }
+}
+
+impl Card {
+    /// Function to get each printing of the card with its set, ordered by release date (see `Printings::printings()`).
+    ///
+    /// What `printings` fetches is kept for the next cards: use the same one for all of them.
+    ///
+    /// # Errors
+    /// If a call fails, it will return a `Err(StatusCode)`.
+    /// To see the possible return values, check the [API docs](https://docs.magicthegathering.io/#documentationerrors).
+    pub async fn fetch_printings(
+        &self,
+        printings: &Printings,
+    ) -> Result<Vec<(Set, Card)>, StatusCode> {
+        printings.printings(self).await
+    }
+
+    /// Function to get each printing of the card with its set, with the given `Client` (see `fetch_printings()`).
+    ///
+    /// Nothing is kept for the next cards: to look at many, hold a `Printings` (see `printings`).
+    ///
+    /// # Example
+    /// ```rust
+    /// use mtgsdk::cards;
+    /// use mtgsdk::client::Client;
+    /// async {
+    ///     let client = Client::new();
+    ///     let card = cards::find_by_name("Opt", Some("XLN")).await.unwrap();
+    ///     let found = card.fetch_printings_with(&client).await.unwrap();
+    ///     assert_eq!(found[0].0.name, "Invasion");
+    /// };
+    /// ```
+    ///
+    /// # Errors
+    /// If a call fails, it will return a `Err(StatusCode)`.
+    /// To see the possible return values, check the [API docs](https://docs.magicthegathering.io/#documentationerrors).
+    pub async fn fetch_printings_with(
+        &self,
+        client: &Client,
+    ) -> Result<Vec<(Set, Card)>, StatusCode> {
+        Printings::with_client(client).printings(self).await
+    }
+
+    /// Function to get the variations of the card (see `Printings::variations()`).
+    ///
+    /// What `printings` fetches is kept for the next cards: use the same one for all of them.
+    ///
+    /// # Errors
+    /// If a call fails, it will return a `Err(StatusCode)`.
+    /// To see the possible return values, check the [API docs](https://docs.magicthegathering.io/#documentationerrors).
+    pub async fn fetch_variations(&self, printings: &Printings) -> Result<Vec<Card>, StatusCode> {
+        printings.variations(self).await
+    }
+
+    /// Function to get the variations of the card with the given `Client` (see `fetch_variations()`).
+    ///
+    /// Nothing is kept for the next cards: to look at many, hold a `Printings` (see `printings`).
+    ///
+    /// # Errors
+    /// If a call fails, it will return a `Err(StatusCode)`.
+    /// To see the possible return values, check the [API docs](https://docs.magicthegathering.io/#documentationerrors).
+    pub async fn fetch_variations_with(&self, client: &Client) -> Result<Vec<Card>, StatusCode> {
+        Printings::with_client(client).variations(self).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cards::Card;
+    use crate::client::tests::Fake;
+    use crate::client::Client;
+    use crate::fixtures::{self, set, set_code, set_codes};
+    use crate::ids::CardId;
+    use crate::printings::Printings;
+    use futures::executor::block_on;
+    use http::StatusCode;
+    use std::sync::Arc;
+
+    fn card(id: &str, name: &str, set: &str) -> Card {
+        Card {
+            id: CardId::parse(id).unwrap(),
+            set_field: set_code(set),
+            printings: set_codes(&["INV", "XLN", "DOM", "PDOM"]),
+            ..fixtures::card(name)
+        }
+    }
+
+    const XLN: &str = "00000000-0000-0000-0000-00000000000a";
+    const INV: &str = "00000000-0000-0000-0000-00000000000b";
+    const DOM: &str = "00000000-0000-0000-0000-00000000000c";
+    const DOM_ALT: &str = "00000000-0000-0000-0000-00000000000d";
+    const MISSING: &str = "00000000-0000-0000-0000-00000000000f";
+
+    fn fake() -> Arc<Fake> {
+        let printings = vec![
+            card(XLN, "Opt", "XLN"),
+            card(INV, "Opt", "INV"),
+            card(DOM_ALT, "Opt", "DOM"),
+            card(DOM, "Opt", "DOM"),
+            card(XLN, "Opt, the Other", "XLN"),
+        ];
+        let cards = serde_json::json!({ "cards": printings }).to_string();
+        let variation = serde_json::json!({ "cards": [card(DOM_ALT, "Opt", "DOM")] }).to_string();
+        let sets = vec![
+            set("XLN", "expansion", None, "2017-09-29"),
+            set("INV", "expansion", None, "2000-10-02"),
+            set("DOM", "expansion", None, "2018-04-27"),
+        ];
+        let sets = serde_json::json!({ "sets": sets }).to_string();
+        Arc::new(
+            Fake::new(&[
+                ("/v1/sets", &sets),
+                ("/v1/cards/?name=%22Opt%22&pageSize=100&page=1", &cards),
+                (
+                    &format!("/v1/cards/?id={}|{}&pageSize=100&page=1", DOM_ALT, MISSING),
+                    &variation,
+                ),
+            ])
+            .status(
+                "/v1/cards/?id=00000000-0000-0000-0000-00000000000e&pageSize=100&page=1",
+                StatusCode::SERVICE_UNAVAILABLE,
+            )
+            .header("Total-Count", "1"),
+        )
+    }
+
+    #[test]
+    fn fetch_printings() {
+        let fake = fake();
+        let printings = Printings::with_client(&Client::with_transport(fake.clone()));
+        let opt = card(DOM, "Opt", "DOM");
+
+        // "PDOM" is not found: left out
+        let found = block_on(printings.printings(&opt)).unwrap();
+        let ids: Vec<(&str, &str)> = found
+            .iter()
+            .map(|(set, card)| (set.code.as_str(), card.id.as_str()))
+            .collect();
+        assert_eq!(ids, vec![("INV", INV), ("XLN", XLN), ("DOM", DOM)]);
+
+        assert_eq!(fake.requests.lock().unwrap().len(), 2);
+
+        // Nothing is fetched again
+        let found = block_on(printings.printings(&card(XLN, "Opt", "XLN"))).unwrap();
+        assert_eq!(found[2].1.id, DOM_ALT);
+        assert_eq!(fake.requests.lock().unwrap().len(), 2);
+
+        block_on(printings.clear());
+        let found = block_on(opt.fetch_printings(&printings));
+        assert_eq!(found.unwrap().len(), 3);
+        assert_eq!(fake.requests.lock().unwrap().len(), 4);
+    }
+
+    #[test]
+    fn fetch_variations() {
+        let fake = fake();
+        let client = Client::with_transport(fake.clone());
+        let printings = Printings::with_client(&client);
+        let mut opt = card(DOM, "Opt", "DOM");
+        opt.variations = Some(vec![
+            DOM_ALT.to_string(),
+            "not an id".to_string(),
+            MISSING.to_string(),
+        ]);
+
+        let found = block_on(printings.variations(&opt)).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, DOM_ALT);
+
+        // Known from the printings
+        block_on(printings.printings(&opt)).unwrap();
+        let sent = fake.requests.lock().unwrap().len();
+        opt.variations = Some(vec![XLN.to_string(), INV.to_string()]);
+        let found = block_on(printings.variations(&opt)).unwrap();
+        let ids: Vec<&str> = found.iter().map(|card| card.id.as_str()).collect();
+        assert_eq!(ids, vec![XLN, INV]);
+        assert_eq!(fake.requests.lock().unwrap().len(), sent);
+
+        opt.variations = Some(vec!["00000000-0000-0000-0000-00000000000e".to_string()]);
+        let error = block_on(opt.fetch_variations(&printings)).unwrap_err();
+        assert_eq!(error, StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    #[test]
+    fn fetch_partial_printings() {
+        // The second page of the printings fails
+        let page =
+            serde_json::json!({ "cards": [card(XLN, "Opt", "XLN"), card(INV, "Opt", "INV")] });
+        let sets = vec![
+            set("XLN", "expansion", None, "2017-09-29"),
+            set("INV", "expansion", None, "2000-10-02"),
+            set("DOM", "expansion", None, "2018-04-27"),
+        ];
+        let sets = serde_json::json!({ "sets": sets }).to_string();
+        let fake = Arc::new(
+            Fake::new(&[
+                ("/v1/sets", &sets),
+                (
+                    "/v1/cards/?name=%22Opt%22&pageSize=100&page=1",
+                    &page.to_string(),
+                ),
+            ])
+            .status(
+                "/v1/cards/?name=%22Opt%22&pageSize=100&page=2",
+                StatusCode::SERVICE_UNAVAILABLE,
+            )
+            .header("Total-Count", "150"),
+        );
+        let client = Client::with_transport(fake.clone());
+        let printings = Printings::with_client(&client);
+
+        // Every set of the card has a printing: the failed page doesn't matter
+        let mut opt = card(XLN, "Opt", "XLN");
+        opt.printings = set_codes(&["XLN", "INV"]);
+        let found = block_on(printings.printings(&opt)).unwrap();
+        assert_eq!(found.len(), 2);
+
+        // The DOM printing may be on the failed page
+        opt.printings = set_codes(&["XLN", "INV", "DOM"]);
+        let error = block_on(opt.fetch_printings_with(&client)).unwrap_err();
+        assert_eq!(error, StatusCode::SERVICE_UNAVAILABLE);
+
+        // Not kept: fetched again
+        let sent = fake.requests.lock().unwrap().len();
+        assert!(block_on(printings.printings(&opt)).is_err());
+        assert!(fake.requests.lock().unwrap().len() > sent);
+    }
+}
@@ -0,0 +1,943 @@
+//! Search cards with the [Scryfall syntax](https://scryfall.com/docs/syntax) (e.g.: `t:legendary c>=rw cmc<=4`).
+//!
+//! `parse()` turns the text into a `Query`. Compiling it splits the query in two: the conditions the API can
+//! express become `cards::filter()` parameters, and the rest (negations, `OR`, comparisons, regular expressions)
+//! is checked on each card returned.
+//!
+//! | Keys | Meaning | Operators |
+//! |---|---|---|
+//! | `name`, or a bare word / `"quoted words"` | name | `:` (contains), `=`, `!=` |
+//! | `t`, `type` | type line | `:`, `=`, `!=` |
+//! | `o`, `oracle` | rules text (`~` is the card's name) | `:`, `=`, `!=` |
+//! | `a`, `artist` | artist | `:`, `=`, `!=` |
+//! | `c`, `color`; `id`, `identity` | colors (e.g.: `rw`, `red`, `c` for colorless, `m` for multicolor) | all |
+//! | `cmc`, `mv`; `pow`, `power`; `tou`, `toughness`; `loy`, `loyalty` | numbers | all |
+//! | `r`, `rarity` | `common`, `uncommon`, `rare`, `mythic`, `special` | all |
+//! | `s`, `e`, `set` | set code | `:`, `=`, `!=` |
+//! | `f`, `format`, `legal`; `banned`; `restricted` | legality in a format | `:` |
+//!
+//! Text values may be regular expressions between slashes (e.g.: `o:/deals \d damage/`), matched ignoring case.
+//! Conditions are joined by `AND` (implicit) or `OR`, negated with `-`, and grouped with parentheses.
+//!
+//! # Example
+//! ```rust
+//! use mtgsdk::query;
+//! async {
+//!     let query = query::parse(r#"t:legendary t:creature c>=rw cmc<=4 o:"draw a card" f:modern -r:common"#).unwrap();
+//!     let cards = query.compile().all().await;
+//!     assert!(cards.unwrap().iter().all(|card| query.matches(card)));
+//! };
+//! ```
+#![allow(dead_code)]
+use crate::cards::{self, color_name, color_symbol, Card, Rarity, Where};
+use crate::client::Client;
+use crate::search::fold;
+use crate::stats::StatValue;
+use futures::future;
+use futures::stream::{Stream, StreamExt, TryStreamExt};
+use http::StatusCode;
+use regex::{Regex, RegexBuilder};
+use std::collections::BTreeSet;
+use std::fmt;
+
+/// Error parsing a query, with the position (in bytes) where it was found.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub message: String,
+    pub position: usize,
+}
+
+impl ParseError {
+    fn new(message: impl Into<String>, position: usize) -> Self {
+        ParseError {
+            message: message.into(),
+            position,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} (at {})", self.message, self.position)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// What a condition looks at.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Key {
+    Name,
+    Type,
+    Oracle,
+    Artist,
+    Color,
+    Identity,
+    ManaValue,
+    Power,
+    Toughness,
+    Loyalty,
+    Rarity,
+    Set,
+    Format,
+    Banned,
+    Restricted,
+}
+
+impl Key {
+    fn parse(key: &str) -> Option<Key> {
+        let key = match key.to_lowercase().as_str() {
+            "name" => Key::Name,
+            "t" | "type" => Key::Type,
+            "o" | "oracle" => Key::Oracle,
+            "a" | "artist" => Key::Artist,
+            "c" | "color" => Key::Color,
+            "id" | "identity" => Key::Identity,
+            "cmc" | "mv" => Key::ManaValue,
+            "pow" | "power" => Key::Power,
+            "tou" | "toughness" => Key::Toughness,
+            "loy" | "loyalty" => Key::Loyalty,
+            "r" | "rarity" => Key::Rarity,
+            "s" | "e" | "set" => Key::Set,
+            "f" | "format" | "legal" => Key::Format,
+            "banned" => Key::Banned,
+            "restricted" => Key::Restricted,
+            _ => return None,
+        };
+        Some(key)
+    }
+
+    fn is_text(self) -> bool {
+        matches!(self, Key::Name | Key::Type | Key::Oracle | Key::Artist)
+    }
+
+    fn is_number(self) -> bool {
+        matches!(
+            self,
+            Key::ManaValue | Key::Power | Key::Toughness | Key::Loyalty
+        )
+    }
+}
+
+/// How a condition compares.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Op {
+    /// `:` (contains, for text; at least, for colors; equals, otherwise)
+    Matches,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+/// Value of a condition.
+#[derive(Clone, Debug)]
+pub enum Value {
+    Text(String),
+    /// Between slashes, matched ignoring case.
+    Regex(Regex),
+}
+
+impl PartialEq for Value {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Value::Text(a), Value::Text(b)) => a == b,
+            (Value::Regex(a), Value::Regex(b)) => a.as_str() == b.as_str(),
+            _ => false,
+        }
+    }
+}
+
+/// A single condition (e.g.: `cmc<=4`).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Condition {
+    pub key: Key,
+    pub op: Op,
+    pub value: Value,
+}
+
+/// Parsed query.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Query {
+    /// Every query matches (an empty `And` matches every card).
+    And(Vec<Query>),
+    /// Any query matches.
+    Or(Vec<Query>),
+    Not(Box<Query>),
+    Condition(Condition),
+}
+
+/// Function to parse a query in the Scryfall syntax (see the module docs).
+///
+/// # Example
+/// ```rust
+/// use mtgsdk::query::{self, Condition, Key, Op, Query, Value};
+///
+/// let query = query::parse("cmc<=4").unwrap();
+/// assert_eq!(query, Query::Condition(Condition { key: Key::ManaValue, op: Op::Le, value: Value::Text("4".to_string()) }));
+/// assert!(query::parse("cmc<=four").is_err());
+/// ```
+///
+/// # Errors
+/// If the query is malformed (e.g.: unknown key, unbalanced quotes or parentheses, invalid value),
+/// it will return a `Err(ParseError)`.
+pub fn parse(input: &str) -> Result<Query, ParseError> {
+    let tokens = lex(input)?;
+    let mut parser = Parser {
+        tokens,
+        position: 0,
+        end: input.len(),
+    };
+    let query = parser.or()?;
+    match parser.tokens.get(parser.position) {
+        Some((at, _)) => Err(ParseError::new("unbalanced ')'", *at)),
+        None => Ok(query),
+    }
+}
+
+impl Query {
+    /// Checks the whole query against a card, without calling the API.
+    pub fn matches(&self, card: &Card) -> bool {
+        match self {
+            Query::And(queries) => queries.iter().all(|q| q.matches(card)),
+            Query::Or(queries) => queries.iter().any(|q| q.matches(card)),
+            Query::Not(query) => !query.matches(card),
+            Query::Condition(condition) => condition.matches(card),
+        }
+    }
+
+    /// Splits the query into API parameters and the conditions left to check locally.
+    ///
+    /// Only the conditions joined by `AND` at the top of the query are sent to the API, each key at most once.
+    pub fn compile(&self) -> Compiled {
+        let conjuncts: Vec<&Query> = match self {
+            Query::And(queries) => queries.iter().collect(),
+            query => vec![query],
+        };
+
+        let mut params: Vec<(&'static str, String)> = Vec::new();
+        let mut residual: Vec<Query> = Vec::new();
+        for query in conjuncts {
+            let pushed = match query {
+                Query::Condition(condition) => condition.pushdown(),
+                _ => None,
+            };
+            match pushed {
+                Some((pushed, exact))
+                    if pushed
+                        .iter()
+                        .all(|(key, _)| params.iter().all(|(k, _)| k != key)) =>
+                {
+                    params.extend(pushed);
+                    if !exact {
+                        residual.push(query.clone());
+                    }
+                }
+                _ => residual.push(query.clone()),
+            }
+        }
+
+        let residual = match residual.len() {
+            0 => None,
+            1 => residual.pop(),
+            _ => Some(Query::And(residual)),
+        };
+        Compiled { params, residual }
+    }
+}
+
+/// A query split into API parameters and local conditions (see `Query::compile()`).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Compiled {
+    params: Vec<(&'static str, String)>,
+    residual: Option<Query>,
+}
+
+impl Compiled {
+    /// Parameters sent to the API (e.g.: `("type", "legendary")`).
+    pub fn params(&self) -> &[(&'static str, String)] {
+        &self.params
+    }
+
+    /// Conditions checked locally, if any.
+    pub fn residual(&self) -> Option<&Query> {
+        self.residual.as_ref()
+    }
+
+    /// The API filter. Without parameters, it gets every card.
+    pub fn filter(&self) -> Where<'_> {
+        self.params
+            .iter()
+            .fold(cards::filter(), |filter, (key, value)| {
+                filter.raw_filter(key, value.clone())
+            })
+    }
+
+    /// Checks the local conditions against a card returned by the API filter.
+    pub fn matches(&self, card: &Card) -> bool {
+        self.residual
+            .as_ref()
+            .is_none_or(|query| query.matches(card))
+    }
+
+    /// Returns every card matching the query, one at a time (see `cards::Where::stream()`).
+    pub fn stream<'a>(
+        &'a self,
+        client: &Client,
+    ) -> impl Stream<Item = Result<Card, StatusCode>> + 'a {
+        self.filter().client(client).stream().filter(move |card| {
+            future::ready(match card {
+                Ok(card) => self.matches(card),
+                Err(_) => true,
+            })
+        })
+    }
+
+    /// Function to get every card matching the query.
+    ///
+    /// # Errors
+    /// If the call fails, it will return a `Err(StatusCode)`.
+    pub async fn all(&self) -> Result<Vec<Card>, StatusCode> {
+        self.all_with(&Client::default()).await
+    }
+
+    /// Function to get every card matching the query, with the given `Client`.
+    ///
+    /// # Errors
+    /// If the call fails, it will return a `Err(StatusCode)`.
+    pub async fn all_with(&self, client: &Client) -> Result<Vec<Card>, StatusCode> {
+        self.stream(client).try_collect().await
+    }
+}
+
+impl Condition {
+    fn matches(&self, card: &Card) -> bool {
+        match self.key {
+            Key::Name => self.text(&card.name, card),
+            Key::Type => self.text(&card.type_field, card),
+            Key::Oracle => self.text(&card.text, card),
+            Key::Artist => self.text(&card.artist, card),
+            Key::Color => self.colors(card.colors.iter().flatten()),
+            Key::Identity => self.colors(card.color_identity.iter().flatten()),
+            Key::ManaValue => self.number(Some(&card.cmc.to_string())),
+            Key::Power => self.number(card.power.as_deref()),
+            Key::Toughness => self.number(card.toughness.as_deref()),
+            Key::Loyalty => self.number(card.loyalty.as_deref()),
+            Key::Rarity => match (
+                Rarity::parse(&card.rarity),
+                self.text_value().and_then(Rarity::parse),
+            ) {
+                (Some(card), Some(value)) => compare(self.op, card.cmp(&value)),
+                _ => false,
+            },
+            Key::Set => {
+                let equal = self
+                    .text_value()
+                    .is_some_and(|set| card.set_field.eq_ignore_ascii_case(set));
+                equal == (self.op != Op::Ne)
+            }
+            Key::Format | Key::Banned | Key::Restricted => {
+                let legality = match self.key {
+                    Key::Format => "Legal",
+                    Key::Banned => "Banned",
+                    _ => "Restricted",
+                };
+                let format = self.text_value().unwrap_or("");
+                card.legalities
+                    .iter()
+                    .any(|l| l.format.eq_ignore_ascii_case(format) && l.legality == legality)
+            }
+        }
+    }
+
+    fn text_value(&self) -> Option<&str> {
+        match &self.value {
+            Value::Text(text) => Some(text),
+            Value::Regex(_) => None,
+        }
+    }
+
+    fn text(&self, field: &str, card: &Card) -> bool {
+        let found = match &self.value {
+            Value::Regex(regex) => regex.is_match(field),
+            Value::Text(text) => {
+                // `~` stands for the card's name
+                let text = if self.key == Key::Oracle {
+                    text.replace('~', &card.name)
+                } else {
+                    text.clone()
+                };
+                let (field, text) = if self.key == Key::Oracle || self.key == Key::Type {
+                    (field.to_lowercase(), text.to_lowercase())
+                } else {
+                    (fold(field), fold(&text))
+                };
+                if self.op == Op::Matches {
+                    field.contains(&text)
+                } else {
+                    field == text
+                }
+            }
+        };
+        found == (self.op != Op::Ne)
+    }
+
+    fn colors<'c>(&self, colors: impl Iterator<Item = &'c String>) -> bool {
+        // Colors are named in full ("Red") in `colors` and by their symbol ("R") in `color_identity`
+        let card: BTreeSet<&str> = colors
+            .filter_map(|c| color_symbol(c).or_else(|| color_name(c).and_then(color_symbol)))
+            .collect();
+        let value = match self.text_value().and_then(parse_colors) {
+            Some(value) => value,
+            None => return false,
+        };
+        let value = match value {
+            Colors::Multicolor => return (card.len() > 1) == (self.op != Op::Ne),
+            // "c:c" is colorless, not "at least no color"
+            Colors::Set(value) if value.is_empty() && self.op == Op::Matches => {
+                return card.is_empty()
+            }
+            Colors::Set(value) => value,
+        };
+        match self.op {
+            Op::Matches | Op::Ge => value.is_subset(&card),
+            Op::Eq => card == value,
+            Op::Ne => card != value,
+            Op::Le => card.is_subset(&value),
+            Op::Gt => value.is_subset(&card) && card != value,
+            Op::Lt => card.is_subset(&value) && card != value,
+        }
+    }
+
+    fn number(&self, field: Option<&str>) -> bool {
+        let (field, value) = match (field, self.text_value()) {
+            (Some(field), Some(value)) => (field, value),
+            _ => return false,
+        };
+        // Variables count as 0 (e.g.: "1+*" is 1), as in Scryfall
+        match (StatValue::parse(field), StatValue::parse(value)) {
+            (Ok(field), Ok(value)) => compare(self.op, field.cmp(&value)),
+            _ => false,
+        }
+    }
+
+    // API parameters for the condition, and whether the API checks it exactly as `matches()` does
+    fn pushdown(&self) -> Option<(Vec<(&'static str, String)>, bool)> {
+        let value = self.text_value()?;
+        let exact = matches!(self.op, Op::Matches | Op::Eq);
+        let param = |key: &'static str, value: String| vec![(key, value)];
+
+        let pushed = match (self.key, self.op) {
+            // The API matches text partially and its own way: the cards are checked again
+            (Key::Name, Op::Matches) => (param("name", value.to_string()), false),
+            (Key::Type, Op::Matches) => (param("type", value.to_string()), false),
+            (Key::Oracle, Op::Matches) if !value.contains('~') => {
+                (param("text", value.to_string()), false)
+            }
+            (Key::Artist, Op::Matches) => (param("artist", value.to_string()), false),
+            (Key::Color, Op::Matches) | (Key::Color, Op::Ge) => {
+                (param("colors", color_names(value)?), false)
+            }
+            (Key::Identity, Op::Matches) | (Key::Identity, Op::Ge) => {
+                (param("colorIdentity", color_symbols(value)?), false)
+            }
+            (Key::ManaValue, _) if exact => {
+                let cmc: f64 = value.parse().ok()?;
+                if cmc.fract() != 0.0 || cmc < 0.0 {
+                    return None;
+                }
+                (param("cmc", (cmc as u64).to_string()), true)
+            }
+            (Key::Power, _) if exact => (param("power", value.to_string()), true),
+            (Key::Toughness, _) if exact => (param("toughness", value.to_string()), true),
+            (Key::Loyalty, _) if exact => (param("loyalty", value.to_string()), true),
+            (Key::Rarity, _) if exact => {
+                let rarity = Rarity::parse(value)?;
+                (param("rarity", rarity.api_name().to_string()), true)
+            }
+            (Key::Set, _) if exact => (param("set", value.to_uppercase()), true),
+            (Key::Format, Op::Matches) => (
+                vec![
+                    ("gameFormat", value.to_string()),
+                    ("legality", "Legal".to_string()),
+                ],
+                true,
+            ),
+            _ => return None,
+        };
+        Some(pushed)
+    }
+}
+
+fn compare(op: Op, ordering: std::cmp::Ordering) -> bool {
+    use std::cmp::Ordering::*;
+    match op {
+        Op::Matches | Op::Eq => ordering == Equal,
+        Op::Ne => ordering != Equal,
+        Op::Lt => ordering == Less,
+        Op::Le => ordering != Greater,
+        Op::Gt => ordering == Greater,
+        Op::Ge => ordering != Less,
+    }
+}
+
+enum Colors {
+    Set(BTreeSet<&'static str>),
+    Multicolor,
+}
+
+// Colors as symbols (e.g.: "rw", "red", "colorless", "m")
+fn parse_colors(value: &str) -> Option<Colors> {
+    let value = value.to_lowercase();
+    match value.as_str() {
+        "c" | "colorless" => return Some(Colors::Set(BTreeSet::new())),
+        "m" | "multicolor" => return Some(Colors::Multicolor),
+        _ => {}
+    }
+    if let Some(symbol) = color_symbol(&value) {
+        return Some(Colors::Set(std::iter::once(symbol).collect()));
+    }
+    value
+        .chars()
+        .map(|c| {
+            let name = color_name(&c.to_string())?;
+            color_symbol(name)
+        })
+        .collect::<Option<BTreeSet<&str>>>()
+        .map(Colors::Set)
+}
+
+// API value for the `colors` filter (e.g.: "red,white"), for plain colors only
+fn color_names(value: &str) -> Option<String> {
+    match parse_colors(value)? {
+        Colors::Set(set) if !set.is_empty() => Some(
+            set.iter()
+                .filter_map(|s| color_name(s))
+                .map(str::to_lowercase)
+                .collect::<Vec<String>>()
+                .join(","),
+        ),
+        _ => None,
+    }
+}
+
+// API value for the `colorIdentity` filter (e.g.: "R,W"), for plain colors only
+fn color_symbols(value: &str) -> Option<String> {
+    match parse_colors(value)? {
+        Colors::Set(set) if !set.is_empty() => {
+            Some(set.into_iter().collect::<Vec<&str>>().join(","))
+        }
+        _ => None,
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Open,
+    Close,
+    Minus,
+    Or,
+    // Bare or quoted word (a name)
+    Word(String),
+    Condition(String, String, Value),
+}
+
+fn lex(input: &str) -> Result<Vec<(usize, Token)>, ParseError> {
+    let mut tokens = Vec::new();
+    let mut chars = input.char_indices().peekable();
+
+    while let Some(&(at, c)) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' => {
+                chars.next();
+                tokens.push((at, Token::Open));
+            }
+            ')' => {
+                chars.next();
+                tokens.push((at, Token::Close));
+            }
+            '-' => {
+                chars.next();
+                tokens.push((at, Token::Minus));
+            }
+            '"' => {
+                chars.next();
+                let (text, _) = delimited(&mut chars, '"', at)?;
+                tokens.push((at, Token::Word(text)));
+            }
+            _ => {
+                let mut word = String::new();
+                while let Some(&(_, c)) = chars.peek() {
+                    if c.is_whitespace()
+                        || c == '('
+                        || c == ')'
+                        || c == '"'
+                        || c == ':'
+                        || c == '='
+                        || c == '<'
+                        || c == '>'
+                        || (c == '!' && !word.is_empty())
+                    {
+                        break;
+                    }
+                    word.push(c);
+                    chars.next();
+                }
+
+                let mut op = String::new();
+                while let Some(&(_, c)) = chars.peek() {
+                    if op.len() < 2 && matches!(c, ':' | '=' | '<' | '>' | '!') {
+                        op.push(c);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+
+                if op.is_empty() {
+                    if word.eq_ignore_ascii_case("or") {
+                        tokens.push((at, Token::Or));
+                    } else if !word.eq_ignore_ascii_case("and") {
+                        tokens.push((at, Token::Word(word)));
+                    }
+                    continue;
+                }
+
+                let value = match chars.peek() {
+                    Some(&(start, '"')) => {
+                        chars.next();
+                        Value::Text(delimited(&mut chars, '"', start)?.0)
+                    }
+                    Some(&(start, '/')) => {
+                        chars.next();
+                        let (pattern, _) = delimited(&mut chars, '/', start)?;
+                        let regex = RegexBuilder::new(&pattern)
+                            .case_insensitive(true)
+                            .build()
+                            .map_err(|e| ParseError::new(format!("invalid regex: {}", e), start))?;
+                        Value::Regex(regex)
+                    }
+                    _ => {
+                        let mut value = String::new();
+                        while let Some(&(_, c)) = chars.peek() {
+                            if c.is_whitespace() || c == ')' {
+                                break;
+                            }
+                            value.push(c);
+                            chars.next();
+                        }
+                        Value::Text(value)
+                    }
+                };
+                tokens.push((at, Token::Condition(word, op, value)));
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+// Text up to the closing delimiter (`\` escapes it)
+fn delimited<I>(
+    chars: &mut std::iter::Peekable<I>,
+    delimiter: char,
+    start: usize,
+) -> Result<(String, usize), ParseError>
+where
+    I: Iterator<Item = (usize, char)>,
+{
+    let mut text = String::new();
+    while let Some((at, c)) = chars.next() {
+        if c == delimiter {
+            return Ok((text, at));
+        }
+        if c == '\\' {
+            if let Some(&(_, next)) = chars.peek() {
+                if next == delimiter {
+                    text.push(next);
+                    chars.next();
+                    continue;
+                }
+            }
+        }
+        text.push(c);
+    }
+    Err(ParseError::new(
+        format!("unbalanced '{}'", delimiter),
+        start,
+    ))
+}
+
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    position: usize,
+    end: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|(_, token)| token)
+    }
+
+    fn at(&self) -> usize {
+        self.tokens
+            .get(self.position)
+            .map_or(self.end, |(at, _)| *at)
+    }
+
+    fn or(&mut self) -> Result<Query, ParseError> {
+        let mut queries = vec![self.and()?];
+        while self.peek() == Some(&Token::Or) {
+            self.position += 1;
+            queries.push(self.and()?);
+        }
+        Ok(if queries.len() == 1 {
+            queries.pop().unwrap()
+        } else {
+            Query::Or(queries)
+        })
+    }
+
+    fn and(&mut self) -> Result<Query, ParseError> {
+        let mut queries = Vec::new();
+        while let Some(token) = self.peek() {
+            if *token == Token::Or || *token == Token::Close {
+                break;
+            }
+            queries.push(self.unary()?);
+        }
+        if queries.is_empty() && self.peek() == Some(&Token::Or) {
+            return Err(ParseError::new("'or' without a condition", self.at()));
+        }
+        Ok(if queries.len() == 1 {
+            queries.pop().unwrap()
+        } else {
+            Query::And(queries)
+        })
+    }
+
+    fn unary(&mut self) -> Result<Query, ParseError> {
+        let at = self.at();
+        let token = match self.tokens.get(self.position) {
+            Some((_, token)) => token.clone(),
+            None => return Err(ParseError::new("missing condition", at)),
+        };
+        self.position += 1;
+
+        match token {
+            Token::Minus => Ok(Query::Not(Box::new(self.unary()?))),
+            Token::Open => {
+                let query = self.or()?;
+                if self.peek() != Some(&Token::Close) {
+                    return Err(ParseError::new("unbalanced '('", at));
+                }
+                self.position += 1;
+                Ok(query)
+            }
+            Token::Word(word) => Ok(Query::Condition(Condition {
+                key: Key::Name,
+                op: Op::Matches,
+                value: Value::Text(word),
+            })),
+            Token::Condition(key, op, value) => condition(&key, &op, value, at),
+            Token::Close | Token::Or => Err(ParseError::new("missing condition", at)),
+        }
+    }
+}
+
+fn condition(key: &str, op: &str, value: Value, at: usize) -> Result<Query, ParseError> {
+    let key =
+        Key::parse(key).ok_or_else(|| ParseError::new(format!("unknown key {:?}", key), at))?;
+    let op = match op {
+        ":" => Op::Matches,
+        "=" => Op::Eq,
+        "!=" => Op::Ne,
+        "<" => Op::Lt,
+        "<=" => Op::Le,
+        ">" => Op::Gt,
+        ">=" => Op::Ge,
+        _ => return Err(ParseError::new(format!("unknown operator {:?}", op), at)),
+    };
+
+    let ordered = matches!(op, Op::Lt | Op::Le | Op::Gt | Op::Ge);
+    let invalid = |what: &str| Err(ParseError::new(what.to_string(), at));
+    match &value {
+        Value::Regex(_) if !key.is_text() => return invalid("regex on a key that is not text"),
+        Value::Regex(_) if op != Op::Matches => {
+            return invalid("regex with an operator other than ':'")
+        }
+        Value::Regex(_) => {}
+        Value::Text(text) if text.is_empty() => return invalid("missing value"),
+        Value::Text(text) => {
+            if (key.is_text() || key == Key::Set) && ordered {
+                return invalid("comparison on a text key");
+            }
+            if matches!(key, Key::Format | Key::Banned | Key::Restricted) && op != Op::Matches {
+                return invalid("legality with an operator other than ':'");
+            }
+            if key.is_number() && StatValue::parse(text).is_err() {
+                return invalid("value that is not a number");
+            }
+            if key == Key::Rarity && Rarity::parse(text).is_none() {
+                return invalid("unknown rarity");
+            }
+            if matches!(key, Key::Color | Key::Identity) {
+                match parse_colors(text) {
+                    None => return invalid("unknown color"),
+                    Some(Colors::Multicolor) if ordered => {
+                        return invalid("comparison with multicolor")
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+    Ok(Query::Condition(Condition { key, op, value }))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cards::{Card, Legalities};
+    use crate::client::tests::Fake;
+    use crate::client::Client;
+    use crate::fixtures::{self, strings};
+    use crate::query::{self, Key, Op, Query, Value};
+    use futures::executor::block_on;
+
+    fn card(name: &str, type_field: &str, colors: &[&str], cmc: f64, text: &str) -> Card {
+        Card {
+            type_field: type_field.to_string(),
+            colors: Some(strings(colors)),
+            cmc,
+            text: text.to_string(),
+            rarity: "Mythic Rare".to_string(),
+            legalities: vec![Legalities {
+                format: "Modern".to_string(),
+                legality: "Legal".to_string(),
+            }],
+            ..fixtures::card(name)
+        }
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(query::parse("").unwrap().matches(&Card::default()));
+        assert!(query::parse("x:y").is_err());
+        assert!(query::parse("o:\"draw").is_err());
+        assert!(query::parse("(t:elf").is_err());
+        assert!(query::parse("t:elf)").is_err());
+        assert!(query::parse("o:/(/").is_err());
+        assert!(query::parse("c:purple").is_err());
+        assert!(query::parse("t>elf").is_err());
+        assert!(query::parse("or t:elf").is_err());
+        assert_eq!(query::parse("x:y").unwrap_err().position, 0);
+        assert_eq!(
+            query::parse("-r:common OR pow>=3").unwrap(),
+            Query::Or(vec![
+                Query::Not(Box::new(Query::Condition(query::Condition {
+                    key: Key::Rarity,
+                    op: Op::Matches,
+                    value: Value::Text("common".to_string()),
+                }))),
+                Query::Condition(query::Condition {
+                    key: Key::Power,
+                    op: Op::Ge,
+                    value: Value::Text("3".to_string()),
+                }),
+            ])
+        );
+    }
+
+    #[test]
+    fn match_locally() {
+        let query = query::parse(
+            r#"t:legendary t:creature c>=rw cmc<=4 o:"draw a card" f:modern -r:common"#,
+        )
+        .unwrap();
+        let mut cards = [
+            card(
+                "Aurelia",
+                "Legendary Creature — Angel",
+                &["Red", "White"],
+                4.0,
+                "Flying. Whenever Aurelia attacks, draw a card.",
+            ),
+            card(
+                "Boros Reckoner",
+                "Creature — Minotaur",
+                &["Red", "White"],
+                3.0,
+                "Draw a card.",
+            ),
+            card(
+                "Feather",
+                "Legendary Creature — Angel",
+                &["Red", "White"],
+                3.0,
+                "Flying",
+            ),
+            card(
+                "Tajic",
+                "Legendary Creature — Human",
+                &["Red"],
+                3.0,
+                "Draw a card.",
+            ),
+        ];
+        cards[0].power = Some("3".to_string());
+        let names: Vec<&str> = cards
+            .iter()
+            .filter(|c| query.matches(c))
+            .map(|c| c.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["Aurelia"]);
+
+        let matches = |q: &str| query::parse(q).unwrap().matches(&cards[0]);
+        assert!(matches("aur"));
+        assert!(matches("o:/^flying\\./"));
+        assert!(matches("o:\"whenever ~ attacks\""));
+        assert!(matches("o:\"~ blocks\" OR f:modern"));
+        assert!(!matches("banned:modern"));
+        assert!(matches("c=wr c<=wrg c!=r c:m pow=3 pow>2.5"));
+        assert!(matches("(t:elf OR t:angel) r>=rare"));
+        assert!(!matches("c:c"));
+        assert!(!matches("tou>1"));
+    }
+
+    #[test]
+    fn compile_and_fetch() {
+        let query = query::parse("t:creature -t:elf f:modern cmc=3 o:/flying/ c:rw").unwrap();
+        let compiled = query.compile();
+        assert_eq!(
+            compiled.params(),
+            &[
+                ("type", "creature".to_string()),
+                ("gameFormat", "modern".to_string()),
+                ("legality", "Legal".to_string()),
+                ("cmc", "3".to_string()),
+                ("colors", "red,white".to_string()),
+            ]
+        );
+
+        let cards = vec![
+            card("Elf", "Creature — Elf", &["Red", "White"], 3.0, "Flying"),
+            card("Bird", "Creature — Bird", &["Red", "White"], 3.0, "Flying"),
+            card("Ox", "Creature — Ox", &["Red", "White"], 3.0, "Vigilance"),
+        ];
+        let body = serde_json::json!({ "cards": cards }).to_string();
+        let fake = Fake::new(&[
+            (
+                "/v1/cards/?type=creature&gameFormat=modern&legality=Legal&cmc=3&colors=red,white&page=1",
+                &body,
+            ),
+        ]);
+        let client = Client::with_transport(fake);
+        let found = block_on(compiled.all_with(&client)).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].name, "Bird");
+    }
+}
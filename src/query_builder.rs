@@ -6,85 +6,93 @@
 //! ```rust
 //! #[tokio::test]
 //! async fn error_404_not_found(){
-//!     let not: Result<formats::RootAll, StatusCode> = query_builder::all("forcenotfound").await;
+//!     let not: Result<formats::RootAll, StatusCode> =
+//!         query_builder::all(&Client::default(), "forcenotfound").await;
 //!     assert_eq!(not.unwrap_err(),StatusCode::NOT_FOUND);
 //! }
 //! ```
-use reqwest::StatusCode;
+use crate::client::Client;
+use http::{HeaderMap, StatusCode};
 use serde::de::DeserializeOwned;
 
-const API_URL: &str = "https://api.magicthegathering.io";
-const API_VER: &str = "v1";
+pub(crate) const API_URL: &str = "https://api.magicthegathering.io";
+pub(crate) const API_VER: &str = "v1";
 
-// Build the URL for all calls
-async fn build<T>(url: String) -> Result<T, StatusCode>
-where
-    T: DeserializeOwned,
-{
-    let response = reqwest::get(url).await;
+// Build the query string (?param=value&param=value), encoding each value once
+pub(crate) fn query_string(query: &[(&str, String)]) -> String {
+    let mut filter = String::new();
 
-    match &response {
-        Ok(r) => {
-            if r.status() != StatusCode::OK {
-                return Err(r.status());
-            }
-        }
-        Err(e) => {
-            if e.is_status() {
-                return Err(e.status().unwrap());
-            } else {
-                return Err(StatusCode::BAD_REQUEST);
-            }
-        }
+    for (k, v) in query.iter() {
+        let separator = if filter.is_empty() { '?' } else { '&' };
+        filter = format!("{}{}{}={}", filter, separator, k, encode_value(v));
     }
+    filter
+}
 
-    // Parse the response body as Json
-    let content = response.unwrap().json::<T>().await;
-
-    match content {
-        Ok(s) => Ok(s),
-        Err(e) => {
-            println!("{:?}", e);
-            Err(StatusCode::BAD_REQUEST)
+// Percent-encode a parameter value, keeping the API's separators (`|` for "or", `,` for "and")
+fn encode_value(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-_.~*|,".contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
         }
     }
+    encoded
 }
 
 // Make call without parameters nor filters
-pub async fn all<T>(call: &str) -> Result<T, StatusCode>
+pub async fn all<T>(client: &Client, call: &str) -> Result<T, StatusCode>
 where
     T: DeserializeOwned,
 {
-    let url = format!("{}/{}/{}", API_URL, API_VER, call);
-    build(url).await
+    client.get(call).await.map(|(t, _)| t)
 }
 
 // Make call with parameter (:/id)
-pub async fn find<T>(call: &str, id: &str) -> Result<T, StatusCode>
+pub async fn find<T>(client: &Client, call: &str, id: &str) -> Result<T, StatusCode>
 where
     T: DeserializeOwned,
 {
-    let url = format!("{}/{}/{}/{}", API_URL, API_VER, call, id);
-    build(url).await
+    let path = format!("{}/{}", call, id);
+    client.get(&path).await.map(|(t, _)| t)
 }
 
 // Make call with query filter (?param=value)
-pub async fn filter<T>(call: &str, params: &str) -> Result<T, StatusCode>
+pub async fn filter<T>(client: &Client, call: &str, params: &str) -> Result<T, StatusCode>
+where
+    T: DeserializeOwned,
+{
+    let path = format!("{}/{}", call, params);
+    client.get(&path).await.map(|(t, _)| t)
+}
+
+// Make call with query filter, keeping the response headers (e.g.: Total-Count), and retry it when refused
+// by the rate limit
+pub(crate) async fn filter_retrying<T>(
+    client: &Client,
+    call: &str,
+    params: &str,
+    retries: u32,
+) -> Result<(T, HeaderMap), StatusCode>
 where
     T: DeserializeOwned,
 {
-    let url = format!("{}/{}/{}/{}", API_URL, API_VER, call, params);
-    build(url).await
+    let path = format!("{}/{}", call, params);
+    client.get_retrying(&path, retries).await
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::client::Client;
     use crate::{formats, query_builder};
-    use reqwest::StatusCode;
+    use http::StatusCode;
 
     #[tokio::test]
     async fn error_404_not_found() {
-        let not: Result<formats::RootAll, StatusCode> = query_builder::all("forcenotfound").await;
+        let not: Result<formats::RootAll, StatusCode> =
+            query_builder::all(&Client::default(), "forcenotfound").await;
         assert_eq!(not.unwrap_err(), StatusCode::NOT_FOUND);
     }
 }
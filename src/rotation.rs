@@ -0,0 +1,364 @@
+//! Standard rotation: which sets were legal in Standard on a given date, and the card pool they made.
+//!
+//! The sets legal in Standard change with each rotation, following rules that changed over the years (see `Rule`).
+//! A `Rotation` is a list of eras, each with its rule, and works out the rotations from the sets' release dates
+//! and types (see `sets::all()`). `Rotation::default()` follows the historical rules, and can be replaced
+//! by other eras (e.g.: to model a past exception or a rule announced since).
+//!
+//! `card_pool()` then gathers the cards printed in those sets, through `Card::printings`
+//! (so a card reprinted later is still in the pool of the set it was first printed in).
+//!
+//! # Example
+//! ```rust
+//! use mtgsdk::date::Date;
+//! use mtgsdk::rotation::Rotation;
+//! use mtgsdk::sets;
+//! async {
+//!     let all = sets::all().await.unwrap();
+//!     let standard = Rotation::default().standard(&all, Date::new(2015, 6, 1).unwrap());
+//!     assert!(standard.iter().any(|set| set.code == "DTK"));
+//!     assert!(!standard.iter().any(|set| set.code == "M14"));
+//! };
+//! ```
+#![allow(dead_code)]
+use crate::cards::Card;
+use crate::client::Client;
+use crate::date::Date;
+use crate::ids::SetCode;
+use crate::sets::{self, Set};
+use http::StatusCode;
+use std::borrow::Borrow;
+use std::collections::{BTreeMap, HashSet};
+
+/// How sets rotate out of Standard.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rule {
+    /// Rotation with the first set of each new block (e.g.: Zendikar, Scars of Mirrodin).
+    /// The sets released since the last `kept` rotations are legal
+    /// (e.g.: with 2, the current and the previous blocks), and of the core sets only the latest one
+    /// (a core set rotates out when the next one is released).
+    Blocks { kept: usize },
+    /// Rotation once a year, with the first set released in `month` or later.
+    /// The sets released since the last `kept` rotations are legal.
+    Yearly { month: u32, kept: usize },
+}
+
+/// Rule followed from a date on (until the next era).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Era {
+    pub from: Date,
+    pub rule: Rule,
+}
+
+impl Era {
+    pub fn new(from: Date, rule: Rule) -> Self {
+        Era { from, rule }
+    }
+}
+
+/// Rotation rules over time, and the set types that enter Standard.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Rotation {
+    eras: Vec<Era>,
+    types: Vec<String>,
+}
+
+impl Default for Rotation {
+    /// The historical rules: two blocks (and the latest core set) until Ixalan,
+    /// then the sets of the last two falls.
+    ///
+    /// The transitions of 2015-2017 (rotations twice a year) are not modeled.
+    fn default() -> Self {
+        Rotation::new(vec![
+            Era::new(Date::new(1995, 1, 1).unwrap(), Rule::Blocks { kept: 2 }),
+            Era::new(
+                Date::new(2017, 9, 29).unwrap(),
+                Rule::Yearly { month: 9, kept: 2 },
+            ),
+        ])
+    }
+}
+
+impl Rotation {
+    /// Rotation with the given eras (in any order), for core sets and expansions.
+    pub fn new(mut eras: Vec<Era>) -> Self {
+        eras.sort_by_key(|era| era.from);
+        Rotation {
+            eras,
+            types: vec!["core".to_string(), "expansion".to_string()],
+        }
+    }
+
+    /// Set types (`Set::type_field`) that enter Standard, replacing "core" and "expansion".
+    pub fn types(mut self, types: &[&str]) -> Self {
+        self.types = types.iter().map(|t| t.to_string()).collect();
+        self
+    }
+
+    /// Era of the given date (`None` before the first one).
+    pub fn era(&self, date: Date) -> Option<&Era> {
+        self.eras.iter().rev().find(|era| era.from <= date)
+    }
+
+    /// Sets legal in Standard on the given date, chronologically.
+    ///
+    /// Online-only sets and sets without a release date are left out.
+    pub fn standard<'a>(&self, sets: &'a [Set], date: Date) -> Vec<&'a Set> {
+        let era = match self.era(date) {
+            Some(era) => era,
+            None => return Vec::new(),
+        };
+        let mut released: Vec<&Set> = sets
+            .iter()
+            .filter(|set| {
+                self.is_standard(set) && set.release_date.is_some_and(|released| released <= date)
+            })
+            .collect();
+        released.sort_by(|a, b| sets::by_release(a, b));
+
+        let rotations = match era.rule {
+            Rule::Blocks { .. } => block_starts(&released),
+            Rule::Yearly { month, .. } => yearly_starts(&released, month),
+        };
+        let kept = match era.rule {
+            Rule::Blocks { kept } | Rule::Yearly { kept, .. } => kept.max(1),
+        };
+        let since = rotations
+            .len()
+            .checked_sub(kept)
+            .map(|at| rotations[at])
+            .or_else(|| rotations.first().copied());
+
+        let latest_core = match era.rule {
+            Rule::Blocks { .. } => released
+                .iter()
+                .rev()
+                .find(|set| set.type_field == "core")
+                .map(|set| set.code.clone()),
+            Rule::Yearly { .. } => None,
+        };
+        released
+            .into_iter()
+            .filter(|set| since.is_none_or(|since| set.release_date >= Some(since)))
+            .filter(|set| match &latest_core {
+                Some(code) => set.type_field != "core" || set.code == *code,
+                None => true,
+            })
+            .collect()
+    }
+
+    /// Cards legal in Standard on the given date, one per name (see `card_pool()`).
+    pub fn pool<'a, I>(&self, sets: &[Set], cards: I, date: Date) -> Vec<&'a Card>
+    where
+        I: IntoIterator<Item = &'a Card>,
+    {
+        card_pool(cards, &self.standard(sets, date))
+    }
+
+    fn is_standard(&self, set: &Set) -> bool {
+        set.online_only != Some(true)
+            && self
+                .types
+                .iter()
+                .any(|t| t.eq_ignore_ascii_case(&set.type_field))
+    }
+}
+
+// Release dates of the first set of each block (expansions only; one without a block starts its own)
+fn block_starts(sets: &[&Set]) -> Vec<Date> {
+    let mut starts = Vec::new();
+    let mut block: Option<&str> = None;
+    for set in sets.iter().filter(|set| set.type_field == "expansion") {
+        let current = set.block.as_deref();
+        if current.is_none() || current != block {
+            starts.extend(set.release_date);
+        }
+        block = current;
+    }
+    starts
+}
+
+// Release dates of the first set released each year in `month` or later
+fn yearly_starts(sets: &[&Set], month: u32) -> Vec<Date> {
+    let mut starts: BTreeMap<i32, Date> = BTreeMap::new();
+    for date in sets.iter().filter_map(|set| set.release_date) {
+        if date.month() >= month {
+            starts.entry(date.year()).or_insert(date);
+        }
+    }
+    starts.into_values().collect()
+}
+
+/// Function to gather the cards printed in the given sets (in any of `Card::printings`), one per name, sorted by name.
+///
+/// Of the printings of a card, the one from one of the sets is kept if there is one.
+pub fn card_pool<'a, I, S>(cards: I, sets: &[S]) -> Vec<&'a Card>
+where
+    I: IntoIterator<Item = &'a Card>,
+    S: Borrow<Set>,
+{
+    let codes: HashSet<&SetCode> = sets.iter().map(|set| &set.borrow().code).collect();
+    let mut pool: BTreeMap<&str, &Card> = BTreeMap::new();
+    for card in cards {
+        if !card.printings.iter().any(|code| codes.contains(code)) {
+            continue;
+        }
+        let in_set = codes.contains(&card.set_field);
+        pool.entry(&card.name)
+            .and_modify(|kept| {
+                if in_set && !codes.contains(&kept.set_field) {
+                    *kept = card;
+                }
+            })
+            .or_insert(card);
+    }
+    pool.into_values().collect()
+}
+
+/// Function to get the sets legal in Standard on the given date, following `Rotation::default()`.
+///
+/// # Example
+/// ```rust
+/// use mtgsdk::date::Date;
+/// use mtgsdk::rotation;
+/// async {
+///    let sets = rotation::standard_sets(Date::new(2015, 6, 1).unwrap()).await;
+///    assert!(sets.unwrap().iter().any(|set| set.name == "Dragons of Tarkir"));
+/// };
+///```
+///
+/// # Errors
+/// If the call fails, it will return a `Err(StatusCode)`.
+/// To see the possible return values, check the [API docs](https://docs.magicthegathering.io/#documentationerrors).
+pub async fn standard_sets(date: Date) -> Result<Vec<Set>, StatusCode> {
+    standard_sets_with(&Client::default(), date).await
+}
+
+/// Function to get the sets legal in Standard on the given date with the given `Client` (see `client`).
+///
+/// # Errors
+/// If the call fails, it will return a `Err(StatusCode)`.
+/// To see the possible return values, check the [API docs](https://docs.magicthegathering.io/#documentationerrors).
+pub async fn standard_sets_with(client: &Client, date: Date) -> Result<Vec<Set>, StatusCode> {
+    let all = sets::all_with(client).await?;
+    let standard = Rotation::default().standard(&all, date);
+    Ok(standard.into_iter().cloned().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cards::Card;
+    use crate::date::Date;
+    use crate::fixtures::{self, codes, set, set_code, set_codes};
+    use crate::rotation::{self, Era, Rotation, Rule};
+    use crate::sets::Set;
+
+    fn sets() -> Vec<Set> {
+        vec![
+            set("RTR", "expansion", Some("Return to Ravnica"), "2012-10-05"),
+            set("GTC", "expansion", Some("Return to Ravnica"), "2013-02-01"),
+            set("DGM", "expansion", Some("Return to Ravnica"), "2013-05-03"),
+            set("M14", "core", Some("Core Set"), "2013-07-19"),
+            set("THS", "expansion", Some("Theros"), "2013-09-27"),
+            set("BNG", "expansion", Some("Theros"), "2014-02-07"),
+            set("JOU", "expansion", Some("Theros"), "2014-05-02"),
+            set("M15", "core", Some("Core Set"), "2014-07-18"),
+            set("KTK", "expansion", Some("Khans of Tarkir"), "2014-09-26"),
+            set("C14", "commander", None, "2014-11-07"),
+            set("FRF", "expansion", Some("Khans of Tarkir"), "2015-01-23"),
+            set("DTK", "expansion", Some("Khans of Tarkir"), "2015-03-27"),
+            set("ORI", "core", Some("Core Set"), "2015-07-17"),
+            set(
+                "BFZ",
+                "expansion",
+                Some("Battle for Zendikar"),
+                "2015-10-02",
+            ),
+            set("KLD", "expansion", Some("Kaladesh"), "2016-09-30"),
+            set("AER", "expansion", Some("Kaladesh"), "2017-01-20"),
+            set("XLN", "expansion", Some("Ixalan"), "2017-09-29"),
+            set("RIX", "expansion", Some("Ixalan"), "2018-01-19"),
+            set("DOM", "expansion", None, "2018-04-27"),
+            set("GRN", "expansion", None, "2018-10-05"),
+            set("ELD", "expansion", None, "2019-10-04"),
+        ]
+    }
+
+    #[test]
+    fn standard_on_date() {
+        let mut all = sets();
+        all.push(Set {
+            release_date: None,
+            ..set("UNK", "expansion", None, "2019-01-01")
+        });
+        let rotation = Rotation::default();
+        let date = |s: &str| s.parse::<Date>().unwrap();
+
+        let standard = rotation.standard(&all, date("2015-06-01"));
+        assert_eq!(
+            codes(&standard),
+            vec!["THS", "BNG", "JOU", "M15", "KTK", "FRF", "DTK"]
+        );
+
+        // The previous core set rotates out with the next one
+        let standard = rotation.standard(&all, date("2014-07-18"));
+        assert_eq!(
+            codes(&standard),
+            vec!["RTR", "GTC", "DGM", "THS", "BNG", "JOU", "M15"]
+        );
+        let standard = rotation.standard(&all, date("2014-07-17"));
+        assert_eq!(
+            codes(&standard),
+            vec!["RTR", "GTC", "DGM", "M14", "THS", "BNG", "JOU"]
+        );
+
+        let standard = rotation.standard(&all, date("2019-10-04"));
+        assert_eq!(codes(&standard), vec!["GRN", "ELD"]);
+        let standard = rotation.standard(&all, date("2018-06-01"));
+        assert_eq!(codes(&standard), vec!["KLD", "AER", "XLN", "RIX", "DOM"]);
+
+        assert!(rotation.standard(&all, date("1990-01-01")).is_empty());
+
+        // Other eras and set types
+        let rotation = Rotation::new(vec![Era::new(date("2000-01-01"), Rule::Blocks { kept: 1 })])
+            .types(&["expansion", "commander"]);
+        let standard = rotation.standard(&all, date("2015-06-01"));
+        assert_eq!(codes(&standard), vec!["KTK", "C14", "FRF", "DTK"]);
+    }
+
+    #[test]
+    fn card_pool() {
+        let all = sets();
+        let card = |name: &str, set: &str, printings: &[&str]| Card {
+            set_field: set_code(set),
+            printings: set_codes(printings),
+            ..fixtures::card(name)
+        };
+        let cards = vec![
+            card("Llanowar Elves", "DOM", &["M14", "M15", "DOM"]),
+            card("Llanowar Elves", "M15", &["M14", "M15", "DOM"]),
+            card("Thoughtseize", "THS", &["THS"]),
+            card("Treasure Cruise", "KTK", &["KTK"]),
+            card("Opt", "XLN", &["XLN", "DOM", "ELD"]),
+        ];
+
+        let date = Date::new(2014, 8, 1).unwrap();
+        let pool = Rotation::default().pool(&all, &cards, date);
+        let names: Vec<(&str, String)> = pool
+            .iter()
+            .map(|card| (card.name.as_str(), card.set_field.to_string()))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("Llanowar Elves", "M15".to_string()),
+                ("Thoughtseize", "THS".to_string())
+            ]
+        );
+
+        let eld: Vec<&Set> = all.iter().filter(|set| set.code == "ELD").collect();
+        let pool = rotation::card_pool(&cards, &eld);
+        assert_eq!(pool.len(), 1);
+        assert_eq!(pool[0].name, "Opt");
+    }
+}
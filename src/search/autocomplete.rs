@@ -0,0 +1,345 @@
+//! Complete card names as they are typed (e.g.: "light" gives "Lightning Bolt", "Lightning Helix").
+//!
+//! An `Autocomplete` keeps the folded names (see `search::fold()`) sorted, so the names starting with a prefix
+//! are found by binary search. Besides `Card::name`, it holds the `ForeignNames::name` of each language.
+//!
+//! It can be saved to disk and loaded back, without crawling the cards again:
+//! ```rust
+//! use mtgsdk::search::autocomplete::{Autocomplete, Ranking};
+//!
+//! let mut index = Autocomplete::new();
+//! index.insert("Lightning Bolt", "English", 3);
+//! let mut saved = Vec::new();
+//! index.write(&mut saved).unwrap();
+//!
+//! let index = Autocomplete::read(saved.as_slice()).unwrap();
+//! assert_eq!(index.complete("light", 5, Ranking::Alphabetical), vec!["Lightning Bolt"]);
+//! ```
+#![allow(dead_code)]
+use super::fold;
+use crate::cards::Card;
+use serde::{Deserialize, Serialize};
+use std::borrow::Borrow;
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap};
+use std::io::{Read, Write};
+
+// Language of `Card::name`
+const ENGLISH: &str = "English";
+
+/// Order of the completions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Ranking {
+    /// Most popular first, then alphabetical.
+    Popularity,
+    /// Alphabetical (folded names).
+    Alphabetical,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct Entry {
+    key: String,
+    name: String,
+    language: String,
+    popularity: u64,
+}
+
+/// Prefix index over card names.
+///
+/// # Example
+/// ```rust
+/// use mtgsdk::cards::Card;
+/// use mtgsdk::search::autocomplete::{Autocomplete, Ranking};
+///
+/// let card = |name: &str| Card { name: name.to_string(), ..Default::default() };
+/// let cards = vec![card("Lightning Helix"), card("Lightning Bolt"), card("Lightning Bolt"), card("Island")];
+/// let index = Autocomplete::from_cards(&cards);
+/// assert_eq!(index.complete("light", 1, Ranking::Popularity), vec!["Lightning Bolt"]);
+/// assert_eq!(index.complete("LIGHT", 2, Ranking::Alphabetical), vec!["Lightning Bolt", "Lightning Helix"]);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Autocomplete {
+    // Sorted by key, then name and language
+    entries: Vec<Entry>,
+}
+
+impl Autocomplete {
+    pub fn new() -> Self {
+        Autocomplete::default()
+    }
+
+    /// Builds the index from the cards' names and foreign names.
+    ///
+    /// The popularity of a name is the number of cards carrying it (e.g.: its printings).
+    pub fn from_cards<I, C>(cards: I) -> Self
+    where
+        I: IntoIterator<Item = C>,
+        C: Borrow<Card>,
+    {
+        let mut counts: HashMap<(String, String), u64> = HashMap::new();
+        for card in cards {
+            let card = card.borrow();
+            *counts
+                .entry((card.name.clone(), ENGLISH.to_string()))
+                .or_default() += 1;
+            for foreign in &card.foreign_names {
+                *counts
+                    .entry((foreign.name.clone(), foreign.language.clone()))
+                    .or_default() += 1;
+            }
+        }
+
+        let mut entries: Vec<Entry> = counts
+            .into_iter()
+            .map(|((name, language), popularity)| Entry {
+                key: fold(&name),
+                name,
+                language,
+                popularity,
+            })
+            .filter(|entry| !entry.key.is_empty())
+            .collect();
+        entries.sort_by(compare);
+        Autocomplete { entries }
+    }
+
+    /// Adds a name (or adds to its popularity, if it is already in the index for `language`).
+    pub fn insert(&mut self, name: &str, language: &str, popularity: u64) {
+        let entry = Entry {
+            key: fold(name),
+            name: name.to_string(),
+            language: language.to_string(),
+            popularity,
+        };
+        if entry.key.is_empty() {
+            return;
+        }
+        match self.entries.binary_search_by(|e| compare(e, &entry)) {
+            Ok(i) => self.entries[i].popularity += popularity,
+            Err(i) => self.entries.insert(i, entry),
+        }
+    }
+
+    /// Replaces the popularity of a name, in every language (e.g.: with how often it is played).
+    pub fn set_popularity(&mut self, name: &str, popularity: u64) {
+        for entry in self.entries.iter_mut().filter(|e| e.name == name) {
+            entry.popularity = popularity;
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns up to `k` names, in any language, starting with `prefix`.
+    pub fn complete(&self, prefix: &str, k: usize, ranking: Ranking) -> Vec<&str> {
+        self.complete_in(prefix, None, k, ranking)
+    }
+
+    /// Returns up to `k` names starting with `prefix`, only in `language` if given (e.g.: "English", "German").
+    pub fn complete_in(
+        &self,
+        prefix: &str,
+        language: Option<&str>,
+        k: usize,
+        ranking: Ranking,
+    ) -> Vec<&str> {
+        let prefix = fold(prefix);
+        let start = self
+            .entries
+            .partition_point(|entry| entry.key.as_str() < prefix.as_str());
+
+        let found = self.entries[start..]
+            .iter()
+            .take_while(|entry| entry.key.starts_with(&prefix))
+            .filter(|entry| language.is_none_or(|l| entry.language.eq_ignore_ascii_case(l)));
+        let names = most_popular_by_name(found);
+
+        match ranking {
+            Ranking::Alphabetical => names.take(k).map(|entry| entry.name.as_str()).collect(),
+            Ranking::Popularity => {
+                // Only the k most popular are kept, the least popular of them on top
+                let mut top: BinaryHeap<Reverse<Popular>> = BinaryHeap::with_capacity(k + 1);
+                for entry in names {
+                    top.push(Reverse(Popular(entry)));
+                    if top.len() > k {
+                        top.pop();
+                    }
+                }
+                top.into_sorted_vec()
+                    .into_iter()
+                    .map(|Reverse(Popular(entry))| entry.name.as_str())
+                    .collect()
+            }
+        }
+    }
+
+    /// Writes the index as JSON.
+    ///
+    /// # Errors
+    /// If writing fails, it will return a `Err(serde_json::Error)`.
+    pub fn write<W: Write>(&self, writer: W) -> serde_json::Result<()> {
+        serde_json::to_writer(writer, self)
+    }
+
+    /// Reads an index written by `write()`.
+    ///
+    /// # Errors
+    /// If reading fails or the JSON is not an index, it will return a `Err(serde_json::Error)`.
+    pub fn read<R: Read>(reader: R) -> serde_json::Result<Self> {
+        let mut index: Autocomplete = serde_json::from_reader(reader)?;
+        // The file may have been written by hand: keep the binary search valid
+        index.entries.sort_by(compare);
+        Ok(index)
+    }
+}
+
+// The same name may be in many languages: its entries are next to each other (same key), keep the most popular one
+fn most_popular_by_name<'a>(
+    entries: impl Iterator<Item = &'a Entry>,
+) -> impl Iterator<Item = &'a Entry> {
+    let mut entries = entries.peekable();
+    std::iter::from_fn(move || {
+        let mut best = entries.next()?;
+        while let Some(entry) = entries.next_if(|entry| entry.name == best.name) {
+            if entry.popularity > best.popularity {
+                best = entry;
+            }
+        }
+        Some(best)
+    })
+}
+
+// Most popular first, then alphabetical: the greatest is the first completion
+struct Popular<'a>(&'a Entry);
+
+impl Ord for Popular<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0
+            .popularity
+            .cmp(&other.0.popularity)
+            .then_with(|| compare(other.0, self.0))
+    }
+}
+
+impl PartialOrd for Popular<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Popular<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Popular<'_> {}
+
+fn compare(a: &Entry, b: &Entry) -> Ordering {
+    a.key
+        .cmp(&b.key)
+        .then_with(|| a.name.cmp(&b.name))
+        .then_with(|| a.language.cmp(&b.language))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cards::{Card, ForeignNames};
+    use crate::fixtures;
+    use crate::search::autocomplete::{Autocomplete, Ranking};
+
+    fn card(name: &str, foreign: &[(&str, &str)]) -> Card {
+        Card {
+            foreign_names: foreign
+                .iter()
+                .map(|(language, name)| ForeignNames {
+                    name: name.to_string(),
+                    text: String::new(),
+                    type_field: None,
+                    flavor: None,
+                    image_url: None,
+                    language: language.to_string(),
+                    multiverseid: None,
+                })
+                .collect(),
+            ..fixtures::card(name)
+        }
+    }
+
+    #[test]
+    fn complete_ranked() {
+        let cards = vec![
+            card("Æther Vial", &[]),
+            card("Aether Spellbomb", &[]),
+            card("Aether Spellbomb", &[]),
+            card(
+                "Lightning Bolt",
+                &[("German", "Blitzschlag"), ("Spanish", "Rayo")],
+            ),
+            card("Llanowar Elves", &[("German", "Llanowarelfen")]),
+        ];
+        let index = Autocomplete::from_cards(&cards);
+
+        assert_eq!(
+            index.complete("ae", 5, Ranking::Alphabetical),
+            vec!["Aether Spellbomb", "Æther Vial"]
+        );
+        assert_eq!(
+            index.complete("aeth", 1, Ranking::Popularity),
+            vec!["Aether Spellbomb"]
+        );
+        assert_eq!(
+            index.complete_in("ll", Some("german"), 5, Ranking::Alphabetical),
+            vec!["Llanowarelfen"]
+        );
+        assert_eq!(
+            index.complete("bli", 5, Ranking::Popularity),
+            vec!["Blitzschlag"]
+        );
+        assert!(index.complete("zz", 5, Ranking::Popularity).is_empty());
+    }
+
+    #[test]
+    fn complete_top_k() {
+        let mut index = Autocomplete::new();
+        for i in 0..50u64 {
+            index.insert(&format!("Goblin {:02}", i), "English", (i * 7) % 11);
+        }
+        index.insert("Goblin 03", "German", 20);
+
+        let top = index.complete("gob", 4, Ranking::Popularity);
+        assert_eq!(
+            top,
+            vec!["Goblin 03", "Goblin 14", "Goblin 25", "Goblin 36"]
+        );
+        assert_eq!(index.complete("gob", 60, Ranking::Popularity).len(), 50);
+        assert!(index.complete("gob", 0, Ranking::Popularity).is_empty());
+        assert_eq!(
+            index.complete("gob", 2, Ranking::Alphabetical),
+            vec!["Goblin 00", "Goblin 01"]
+        );
+    }
+
+    #[test]
+    fn insert_and_round_trip() {
+        let mut index = Autocomplete::new();
+        index.insert("Island", "English", 1);
+        index.insert("Llanowar Elves", "English", 2);
+        index.insert("Island", "English", 5);
+        index.set_popularity("Llanowar Elves", 10);
+        assert_eq!(index.len(), 2);
+        assert_eq!(
+            index.complete("", 2, Ranking::Popularity),
+            vec!["Llanowar Elves", "Island"]
+        );
+
+        let mut saved = Vec::new();
+        index.write(&mut saved).unwrap();
+        assert_eq!(Autocomplete::read(saved.as_slice()).unwrap(), index);
+    }
+}
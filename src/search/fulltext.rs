@@ -0,0 +1,626 @@
+//! Search the cards' rules text, rulings and foreign texts (e.g.: `"whenever ~ deals combat damage"`).
+//!
+//! A `TextIndex` indexes, for each card name, `Card::text`, `Card::original_text`, the `Rulings::text`,
+//! and the `ForeignNames::text` and `ForeignNames::flavor`. The words are folded (see `search::fold()`)
+//! and stemmed, so "deals", "dealt" and "dealing" all match "deal". In the texts, the card's own name is
+//! replaced by `~`, so `~` in a query stands for "this card".
+//!
+//! Queries:
+//! - `combat damage`: cards with both words (anywhere);
+//! - `"combat damage"`: cards with the phrase;
+//! - `flying OR reach`: cards with either word;
+//! - `-flying`, `NOT flying`: cards without the word;
+//! - `(flying OR reach) -defender`: parentheses group.
+//!
+//! The results are ranked by relevance ([BM25](https://en.wikipedia.org/wiki/Okapi_BM25)), the rules text
+//! weighing more than rulings, which weigh more than foreign texts.
+#![allow(dead_code)]
+use super::fold;
+use crate::cards::Card;
+use crate::ids::CardId;
+use std::borrow::Borrow;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+// BM25 parameters
+const K1: f64 = 1.2;
+const B: f64 = 0.75;
+
+// Weight of each field in the relevance
+const TEXT_WEIGHT: f64 = 1.0;
+const RULING_WEIGHT: f64 = 0.5;
+const FOREIGN_WEIGHT: f64 = 0.3;
+
+// Positions between two texts of the same card, so that phrases do not span them
+const TEXT_GAP: u32 = 1000;
+
+// Token standing for the card's own name
+const SELF: &str = "~";
+
+/// A card matching a query, with its relevance (higher is better).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Hit<'a> {
+    pub name: &'a str,
+    pub id: &'a CardId,
+    pub score: f64,
+}
+
+#[derive(Clone, Debug)]
+struct Doc {
+    name: String,
+    id: CardId,
+    // Weighted number of words
+    length: f64,
+}
+
+#[derive(Clone, Debug)]
+struct Posting {
+    doc: usize,
+    positions: Vec<u32>,
+    // Occurrences, weighted by field
+    frequency: f64,
+}
+
+/// Full-text index over the cards' texts.
+///
+/// # Example
+/// ```rust
+/// use mtgsdk::cards::Card;
+/// use mtgsdk::search::fulltext::TextIndex;
+///
+/// let ninja = Card {
+///     name: "Ninja of the Deep Hours".to_string(),
+///     text: "Whenever Ninja of the Deep Hours deals combat damage to a player, you may draw a card.".to_string(),
+///     ..Default::default()
+/// };
+/// let index = TextIndex::from_cards(vec![ninja]);
+/// let hits = index.search("\"whenever ~ deals combat damage\"", 10);
+/// assert_eq!(hits[0].name, "Ninja of the Deep Hours");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct TextIndex {
+    docs: Vec<Doc>,
+    postings: HashMap<String, Vec<Posting>>,
+    average_length: f64,
+}
+
+impl TextIndex {
+    /// Builds the index from the cards. The printings of a card (same name) are indexed once.
+    pub fn from_cards<I, C>(cards: I) -> Self
+    where
+        I: IntoIterator<Item = C>,
+        C: Borrow<Card>,
+    {
+        let mut index = TextIndex::default();
+        let mut seen: BTreeSet<String> = BTreeSet::new();
+
+        for card in cards {
+            let card = card.borrow();
+            if seen.insert(card.name.clone()) {
+                index.add(card);
+            }
+        }
+
+        let total: f64 = index.docs.iter().map(|doc| doc.length).sum();
+        index.average_length = if index.docs.is_empty() {
+            0.0
+        } else {
+            total / index.docs.len() as f64
+        };
+        index
+    }
+
+    fn add(&mut self, card: &Card) {
+        let doc = self.docs.len();
+        let mut texts: Vec<(&str, f64)> = vec![(&card.text, TEXT_WEIGHT)];
+        if let Some(text) = &card.original_text {
+            texts.push((text, TEXT_WEIGHT));
+        }
+        for ruling in card.rulings.iter().flatten() {
+            texts.push((&ruling.text, RULING_WEIGHT));
+        }
+        for foreign in &card.foreign_names {
+            texts.push((&foreign.text, FOREIGN_WEIGHT));
+            if let Some(flavor) = &foreign.flavor {
+                texts.push((flavor, FOREIGN_WEIGHT));
+            }
+        }
+
+        let mut terms: BTreeMap<String, Posting> = BTreeMap::new();
+        let mut length = 0.0;
+        for (i, (text, weight)) in texts.into_iter().enumerate() {
+            let start = i as u32 * TEXT_GAP;
+            for (position, term) in analyze(&name_to_self(text, &card.name))
+                .into_iter()
+                .enumerate()
+            {
+                let posting = terms.entry(term).or_insert_with(|| Posting {
+                    doc,
+                    positions: Vec::new(),
+                    frequency: 0.0,
+                });
+                posting.positions.push(start + position as u32);
+                posting.frequency += weight;
+                length += weight;
+            }
+        }
+
+        for (term, posting) in terms {
+            self.postings.entry(term).or_default().push(posting);
+        }
+        self.docs.push(Doc {
+            name: card.name.clone(),
+            id: card.id.clone(),
+            length,
+        });
+    }
+
+    pub fn len(&self) -> usize {
+        self.docs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.docs.is_empty()
+    }
+
+    /// Returns up to `limit` cards matching `query` (see the module docs for the syntax), most relevant first.
+    pub fn search(&self, query: &str, limit: usize) -> Vec<Hit<'_>> {
+        let query = match parse(query) {
+            Some(query) => query,
+            None => return Vec::new(),
+        };
+
+        let mut hits: Vec<Hit<'_>> = self
+            .eval(&query)
+            .into_iter()
+            .map(|(doc, score)| Hit {
+                name: &self.docs[doc].name,
+                id: &self.docs[doc].id,
+                score,
+            })
+            .collect();
+        hits.sort_by(|a, b| {
+            b.score
+                .partial_cmp(&a.score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.name.cmp(b.name))
+        });
+        hits.truncate(limit);
+        hits
+    }
+
+    fn eval(&self, query: &Query) -> BTreeMap<usize, f64> {
+        match query {
+            Query::Phrase(terms) => self.phrase(terms),
+            Query::And(queries) => {
+                let mut result: Option<BTreeMap<usize, f64>> = None;
+                let mut excluded: BTreeSet<usize> = BTreeSet::new();
+                for query in queries {
+                    if let Query::Not(negated) = query {
+                        excluded.extend(self.eval(negated).into_keys());
+                        continue;
+                    }
+                    let scores = self.eval(query);
+                    result = Some(match result {
+                        None => scores,
+                        Some(result) => result
+                            .into_iter()
+                            .filter_map(|(doc, score)| scores.get(&doc).map(|s| (doc, score + s)))
+                            .collect(),
+                    });
+                }
+                // Only exclusions: every other card matches
+                let result =
+                    result.unwrap_or_else(|| (0..self.docs.len()).map(|doc| (doc, 0.0)).collect());
+                result
+                    .into_iter()
+                    .filter(|(doc, _)| !excluded.contains(doc))
+                    .collect()
+            }
+            Query::Or(queries) => {
+                let mut result: BTreeMap<usize, f64> = BTreeMap::new();
+                for query in queries {
+                    for (doc, score) in self.eval(query) {
+                        *result.entry(doc).or_default() += score;
+                    }
+                }
+                result
+            }
+            Query::Not(negated) => {
+                let excluded = self.eval(negated);
+                (0..self.docs.len())
+                    .filter(|doc| !excluded.contains_key(doc))
+                    .map(|doc| (doc, 0.0))
+                    .collect()
+            }
+        }
+    }
+
+    // Cards with the terms in sequence, scored on the number of times the sequence occurs
+    fn phrase(&self, terms: &[String]) -> BTreeMap<usize, f64> {
+        let lists: Option<Vec<&Vec<Posting>>> =
+            terms.iter().map(|t| self.postings.get(t)).collect();
+        let lists = match lists {
+            Some(lists) if !lists.is_empty() => lists,
+            _ => return BTreeMap::new(),
+        };
+
+        let mut result = BTreeMap::new();
+        for first in lists[0] {
+            let postings: Option<Vec<&Posting>> = lists[1..]
+                .iter()
+                .map(|list| {
+                    list.binary_search_by_key(&first.doc, |p| p.doc)
+                        .ok()
+                        .map(|i| &list[i])
+                })
+                .collect();
+            let postings = match postings {
+                Some(postings) => postings,
+                None => continue,
+            };
+
+            let frequency = if postings.is_empty() {
+                first.frequency
+            } else {
+                first
+                    .positions
+                    .iter()
+                    .filter(|&&start| {
+                        postings.iter().enumerate().all(|(i, p)| {
+                            p.positions.binary_search(&(start + i as u32 + 1)).is_ok()
+                        })
+                    })
+                    .count() as f64
+            };
+            if frequency == 0.0 {
+                continue;
+            }
+
+            let score: f64 = lists
+                .iter()
+                .map(|list| self.bm25(list.len(), frequency, first.doc))
+                .sum();
+            result.insert(first.doc, score);
+        }
+        result
+    }
+
+    fn bm25(&self, documents_with_term: usize, frequency: f64, doc: usize) -> f64 {
+        let n = self.docs.len() as f64;
+        let df = documents_with_term as f64;
+        let idf = (1.0 + (n - df + 0.5) / (df + 0.5)).ln();
+        let length = self.docs[doc].length / self.average_length.max(1.0);
+        idf * frequency * (K1 + 1.0) / (frequency + K1 * (1.0 - B + B * length))
+    }
+}
+
+// Replace the card's name (and its short name, e.g.: "Narset" for "Narset, Enlightened Master") by `~`
+fn name_to_self(text: &str, name: &str) -> String {
+    let mut text = replace_ignore_case(text, name);
+    if let Some((short, _)) = name.split_once(", ") {
+        text = replace_ignore_case(&text, short);
+    }
+    text
+}
+
+// Only whole words are replaced: "Ral" in "Ral's bolt", not in "several"
+fn replace_ignore_case(text: &str, name: &str) -> String {
+    if name.is_empty() {
+        return text.to_string();
+    }
+    let lower = text.to_lowercase();
+    let needle = name.to_lowercase();
+    // Lowercasing may change lengths (e.g.: 'İ'), in which case the text is left as is
+    if lower.len() != text.len() {
+        return text.to_string();
+    }
+
+    let mut replaced = String::with_capacity(text.len());
+    let mut last = 0;
+    let is_word = |c: Option<char>| c.is_some_and(char::is_alphanumeric);
+    for (i, _) in lower.match_indices(&needle) {
+        let end = i + needle.len();
+        if is_word(text[..i].chars().next_back()) || is_word(text[end..].chars().next()) {
+            continue;
+        }
+        replaced.push_str(&text[last..i]);
+        replaced.push_str(" ~ ");
+        last = end;
+    }
+    replaced.push_str(&text[last..]);
+    replaced
+}
+
+// Folded and stemmed words, keeping `~`
+fn analyze(text: &str) -> Vec<String> {
+    let mut terms = Vec::new();
+    for (i, part) in text.split('~').enumerate() {
+        if i > 0 {
+            terms.push(SELF.to_string());
+        }
+        terms.extend(fold(part).split_whitespace().map(stem));
+    }
+    terms
+}
+
+// Light English stemmer: plurals, -ed, -ing, final e
+fn stem(word: &str) -> String {
+    if word.len() <= 3 || !word.is_ascii() {
+        return word.to_string();
+    }
+    let mut w = word.to_string();
+
+    if w.ends_with("ies") && w.len() > 4 {
+        w.truncate(w.len() - 3);
+        w.push('y');
+    } else if w.ends_with("sses") {
+        w.truncate(w.len() - 2);
+    } else if w.ends_with('s') && !w.ends_with("ss") && !w.ends_with("us") {
+        w.pop();
+    }
+
+    let stripped = if w.ends_with("ing") && w.len() >= 6 {
+        w.truncate(w.len() - 3);
+        true
+    } else if w.ends_with("ed") && w.len() >= 5 {
+        w.truncate(w.len() - 2);
+        true
+    } else {
+        false
+    };
+    if stripped {
+        // "hitting" is "hit", "dealt" stays as is
+        let bytes = w.as_bytes();
+        let n = bytes.len();
+        if n >= 2 && bytes[n - 1] == bytes[n - 2] && !b"lsz".contains(&bytes[n - 1]) {
+            w.pop();
+        }
+    }
+
+    if w.ends_with('e') && w.len() > 3 {
+        w.pop();
+    }
+    w
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Query {
+    // A single word is a phrase of one term
+    Phrase(Vec<String>),
+    And(Vec<Query>),
+    Or(Vec<Query>),
+    Not(Box<Query>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Word(String),
+    Quoted(String),
+    Open,
+    Close,
+    Minus,
+}
+
+fn lex(query: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars = query.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' | ')' => {
+                chars.next();
+                tokens.push(if c == '(' { Token::Open } else { Token::Close });
+            }
+            '"' => {
+                chars.next();
+                // An unbalanced quote runs to the end of the query
+                let phrase: String = chars.by_ref().take_while(|&c| c != '"').collect();
+                tokens.push(Token::Quoted(phrase));
+            }
+            '-' => {
+                chars.next();
+                tokens.push(Token::Minus);
+            }
+            _ => {
+                let mut word = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c.is_whitespace() || c == '(' || c == ')' || c == '"' {
+                        break;
+                    }
+                    word.push(c);
+                    chars.next();
+                }
+                tokens.push(Token::Word(word));
+            }
+        }
+    }
+    tokens
+}
+
+fn parse(query: &str) -> Option<Query> {
+    let tokens = lex(query);
+    let mut position = 0;
+    let mut parts = Vec::new();
+    // Unmatched closing parentheses are skipped
+    while position < tokens.len() {
+        match parse_or(&tokens, &mut position) {
+            Some(query) => parts.push(query),
+            None => position += 1,
+        }
+    }
+    match parts.len() {
+        0 => None,
+        1 => parts.pop(),
+        _ => Some(Query::And(parts)),
+    }
+}
+
+fn parse_or(tokens: &[Token], position: &mut usize) -> Option<Query> {
+    let mut parts = vec![parse_and(tokens, position)?];
+    while tokens.get(*position) == Some(&Token::Word("OR".to_string())) {
+        *position += 1;
+        if let Some(query) = parse_and(tokens, position) {
+            parts.push(query);
+        }
+    }
+    Some(if parts.len() == 1 {
+        parts.pop().unwrap()
+    } else {
+        Query::Or(parts)
+    })
+}
+
+fn parse_and(tokens: &[Token], position: &mut usize) -> Option<Query> {
+    let mut parts = Vec::new();
+    while let Some(token) = tokens.get(*position) {
+        match token {
+            Token::Close => break,
+            Token::Word(w) if w == "OR" => break,
+            Token::Word(w) if w == "AND" => *position += 1,
+            _ => {
+                if let Some(query) = parse_unary(tokens, position) {
+                    parts.push(query);
+                }
+            }
+        }
+    }
+    match parts.len() {
+        0 => None,
+        1 => parts.pop(),
+        _ => Some(Query::And(parts)),
+    }
+}
+
+fn parse_unary(tokens: &[Token], position: &mut usize) -> Option<Query> {
+    let token = tokens.get(*position)?.clone();
+    *position += 1;
+    match token {
+        Token::Minus => parse_unary(tokens, position).map(|q| Query::Not(Box::new(q))),
+        Token::Word(w) if w == "NOT" => {
+            parse_unary(tokens, position).map(|q| Query::Not(Box::new(q)))
+        }
+        Token::Open => {
+            let query = parse_or(tokens, position);
+            if tokens.get(*position) == Some(&Token::Close) {
+                *position += 1;
+            }
+            query
+        }
+        Token::Close => None,
+        Token::Word(text) | Token::Quoted(text) => {
+            let terms = analyze(&text);
+            if terms.is_empty() {
+                None
+            } else {
+                Some(Query::Phrase(terms))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cards::{Card, Rulings};
+    use crate::fixtures;
+    use crate::search::fulltext::{self, Query, TextIndex};
+
+    fn card(name: &str, text: &str) -> Card {
+        Card {
+            text: text.to_string(),
+            ..fixtures::card(name)
+        }
+    }
+
+    fn index() -> TextIndex {
+        let mut ninja = card(
+            "Ninja of the Deep Hours",
+            "Ninjutsu {1}{U}\nWhenever Ninja of the Deep Hours deals combat damage to a player, you may draw a card.",
+        );
+        ninja.rulings = Some(vec![Rulings {
+            date: "2004-12-01".to_string(),
+            text: "Ninjutsu can be activated only after blockers are declared.".to_string(),
+        }]);
+        TextIndex::from_cards(vec![
+            ninja.clone(),
+            ninja,
+            card("Prodigal Sorcerer", "{T}: Prodigal Sorcerer deals 1 damage to any target."),
+            card("Giant Spider", "Reach"),
+            card("Serra Angel", "Flying\nVigilance"),
+            card(
+                "Narset, Enlightened Master",
+                "First strike, hexproof\nWhenever Narset attacks, exile the top four cards of your library.",
+            ),
+        ])
+    }
+
+    fn names(index: &TextIndex, query: &str) -> Vec<String> {
+        index
+            .search(query, 10)
+            .into_iter()
+            .map(|hit| hit.name.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn stem_and_parse() {
+        assert_eq!(fulltext::stem("deals"), "deal");
+        assert_eq!(fulltext::stem("dealing"), "deal");
+        assert_eq!(fulltext::stem("creatures"), fulltext::stem("creature"));
+        assert_eq!(fulltext::stem("hitting"), "hit");
+        assert_eq!(fulltext::stem("abilities"), "ability");
+        assert_eq!(
+            fulltext::parse("(flying OR reach) -defender"),
+            Some(Query::And(vec![
+                Query::Or(vec![
+                    Query::Phrase(vec!["fly".to_string()]),
+                    Query::Phrase(vec!["reach".to_string()]),
+                ]),
+                Query::Not(Box::new(Query::Phrase(vec!["defender".to_string()]))),
+            ]))
+        );
+    }
+
+    #[test]
+    fn search_queries() {
+        let index = index();
+        assert_eq!(index.len(), 5);
+        assert_eq!(
+            names(&index, "\"whenever ~ deals combat damage\""),
+            vec!["Ninja of the Deep Hours"]
+        );
+        assert_eq!(
+            names(&index, "\"whenever ~ attacks\""),
+            vec!["Narset, Enlightened Master"]
+        );
+        assert_eq!(names(&index, "\"~ deals\"").len(), 2);
+        assert_eq!(names(&index, "damage -combat"), vec!["Prodigal Sorcerer"]);
+        assert_eq!(
+            names(&index, "flying OR reach"),
+            vec!["Giant Spider", "Serra Angel"]
+        );
+        assert_eq!(names(&index, "NOT damage").len(), 3);
+        assert_eq!(
+            names(&index, "blockers declared"),
+            vec!["Ninja of the Deep Hours"]
+        );
+        assert!(names(&index, "\"damage combat\"").is_empty());
+        assert!(names(&index, "\"").is_empty());
+    }
+
+    #[test]
+    fn name_in_other_words() {
+        assert_eq!(
+            fulltext::name_to_self(
+                "Ral deals 2 damage to several general targets. Ral's",
+                "Ral, Caller of Storms"
+            ),
+            " ~  deals 2 damage to several general targets.  ~ 's"
+        );
+        assert_eq!(
+            fulltext::name_to_self("Reach, overreach", "Reach"),
+            " ~ , overreach"
+        );
+    }
+}
@@ -0,0 +1,320 @@
+//! Resolve misspelled card names (e.g.: "lightnig bolt", "jace mind sculptor").
+//!
+//! A `NameIndex` holds the card names known locally and ranks them against what the user typed,
+//! by edit distance over the whole name and by matching its words in any order.
+//! Names are compared folded (see `search::fold()`), so "lim dul" finds "Lim-Dûl the Necromancer".
+//!
+//! Cards printed with two faces on a single card (e.g.: split cards) are also known by their combined name,
+//! so "fire ice" and "Fire // Ice" find "Fire // Ice".
+#![allow(dead_code)]
+use super::{fold, similarity, tokens};
+use crate::cards::Card;
+use std::borrow::Borrow;
+use std::collections::{BTreeMap, HashMap};
+
+// Below this score, a name is not a candidate
+const MIN_SCORE: f64 = 0.5;
+// The best candidate is taken as the answer from this score on...
+const RESOLVE_SCORE: f64 = 0.85;
+// ...when it is ahead of the next one by this much
+const RESOLVE_MARGIN: f64 = 0.05;
+
+// Layouts whose faces are named together as "Left // Right"
+const MULTI_FACE_LAYOUTS: [&str; 7] = [
+    "split",
+    "aftermath",
+    "flip",
+    "adventure",
+    "transform",
+    "modal_dfc",
+    "double-faced",
+];
+
+#[derive(Clone, Debug)]
+struct Entry {
+    name: String,
+    folded: String,
+    tokens: Vec<String>,
+}
+
+/// A name matching a query, with its score between 0 and 1 (1 is an exact match).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Candidate<'a> {
+    pub name: &'a str,
+    pub score: f64,
+}
+
+/// Index of card names for fuzzy resolution.
+///
+/// # Example
+/// ```rust
+/// use mtgsdk::search::fuzzy::NameIndex;
+///
+/// let index = NameIndex::from_names(vec!["Lightning Bolt", "Lightning Helix", "Jace, the Mind Sculptor"]);
+/// assert_eq!(index.resolve("lightnig bolt"), Some("Lightning Bolt"));
+/// assert_eq!(index.resolve("jace mind sculptor"), Some("Jace, the Mind Sculptor"));
+/// assert_eq!(index.did_you_mean("lightning", 2), vec!["Lightning Bolt", "Lightning Helix"]);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct NameIndex {
+    entries: Vec<Entry>,
+    exact: HashMap<String, usize>,
+}
+
+impl NameIndex {
+    pub fn new() -> Self {
+        NameIndex::default()
+    }
+
+    /// Builds the index from a list of names.
+    pub fn from_names<I, S>(names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut index = NameIndex::new();
+        for name in names {
+            index.insert(name.as_ref());
+        }
+        index
+    }
+
+    /// Builds the index from the names of the cards (e.g.: `Store::cards()`).
+    ///
+    /// The faces of a card with two faces are added one by one, and together as "Left // Right",
+    /// with the faces in the order of their collector numbers (e.g.: "51a" before "51b").
+    pub fn from_cards<I, C>(cards: I) -> Self
+    where
+        I: IntoIterator<Item = C>,
+        C: Borrow<Card>,
+    {
+        let mut index = NameIndex::new();
+        // Faces of each multi-face card, with the collector number of their first printing
+        let mut faces: BTreeMap<Vec<String>, HashMap<String, String>> = BTreeMap::new();
+
+        for card in cards {
+            let card = card.borrow();
+            index.insert(&card.name);
+
+            if let Some(names) = &card.names {
+                if names.len() > 1 && MULTI_FACE_LAYOUTS.contains(&card.layout.as_str()) {
+                    let mut key: Vec<String> = names.iter().cloned().collect();
+                    key.sort();
+                    faces
+                        .entry(key)
+                        .or_default()
+                        .entry(card.name.clone())
+                        .or_insert_with(|| card.number.clone());
+                }
+            }
+        }
+
+        for (names, numbers) in faces {
+            let mut ordered: Vec<(&str, &str)> = names
+                .iter()
+                .map(|name| {
+                    let number = numbers.get(name).map(String::as_str).unwrap_or("");
+                    (number, name.as_str())
+                })
+                .collect();
+            ordered.sort();
+            let combined: Vec<&str> = ordered.into_iter().map(|(_, name)| name).collect();
+            index.insert(&combined.join(" // "));
+        }
+        index
+    }
+
+    /// Adds a name. Names already in the index (once folded) are ignored.
+    pub fn insert(&mut self, name: &str) {
+        let folded = fold(name);
+        if folded.is_empty() || self.exact.contains_key(&folded) {
+            return;
+        }
+        self.exact.insert(folded.clone(), self.entries.len());
+        self.entries.push(Entry {
+            name: name.to_string(),
+            tokens: tokens(&folded),
+            folded,
+        });
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns up to `limit` names matching `query`, best first.
+    pub fn candidates(&self, query: &str, limit: usize) -> Vec<Candidate<'_>> {
+        let folded = fold(query);
+        if folded.is_empty() {
+            return Vec::new();
+        }
+        let query_tokens = tokens(&folded);
+
+        let mut candidates: Vec<Candidate<'_>> = self
+            .entries
+            .iter()
+            .map(|entry| Candidate {
+                name: &entry.name,
+                score: score(&folded, &query_tokens, entry),
+            })
+            .filter(|candidate| candidate.score >= MIN_SCORE)
+            .collect();
+        candidates.sort_by(|a, b| {
+            b.score
+                .partial_cmp(&a.score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.name.cmp(b.name))
+        });
+        candidates.truncate(limit);
+        candidates
+    }
+
+    /// Returns the name meant by `query`: its exact match (once folded), or else the best candidate
+    /// if it is close enough and clearly ahead of the others.
+    pub fn resolve(&self, query: &str) -> Option<&str> {
+        if let Some(&i) = self.exact.get(&fold(query)) {
+            return Some(&self.entries[i].name);
+        }
+
+        let candidates = self.candidates(query, 2);
+        let best = candidates.first()?;
+        let runner_up = candidates.get(1).map_or(0.0, |c| c.score);
+        if best.score >= RESOLVE_SCORE && best.score - runner_up >= RESOLVE_MARGIN {
+            Some(best.name)
+        } else {
+            None
+        }
+    }
+
+    /// Returns up to `limit` suggestions for `query`, best first, or nothing if `query` is a known name.
+    pub fn did_you_mean(&self, query: &str, limit: usize) -> Vec<&str> {
+        if self.exact.contains_key(&fold(query)) {
+            return Vec::new();
+        }
+        self.candidates(query, limit)
+            .into_iter()
+            .map(|candidate| candidate.name)
+            .collect()
+    }
+}
+
+fn score(query: &str, query_tokens: &[String], entry: &Entry) -> f64 {
+    if entry.folded == query {
+        return 1.0;
+    }
+    let whole = similarity(query, &entry.folded);
+    let words = token_score(query_tokens, &entry.tokens);
+    // Slightly below the whole name, so that a typo in the full name ranks above a partial name
+    whole.max(words * 0.95)
+}
+
+// How well each word of the query matches a word of the name, in any order
+fn token_score(query: &[String], name: &[String]) -> f64 {
+    if query.is_empty() || name.is_empty() {
+        return 0.0;
+    }
+
+    let mut used = vec![false; name.len()];
+    let mut total = 0.0;
+    for word in query {
+        let best = name
+            .iter()
+            .enumerate()
+            .filter(|(j, _)| !used[*j])
+            .map(|(j, token)| {
+                let s = if token == word {
+                    1.0
+                } else if word.len() >= 3 && token.starts_with(word.as_str()) {
+                    0.9
+                } else {
+                    similarity(word, token)
+                };
+                (j, s)
+            })
+            .fold(None, |best: Option<(usize, f64)>, (j, s)| match best {
+                Some((_, b)) if b >= s => best,
+                _ => Some((j, s)),
+            });
+        if let Some((j, s)) = best {
+            used[j] = true;
+            total += s;
+        }
+    }
+
+    let matched = total / query.len() as f64;
+    // Names with words missing from the query rank below the ones without
+    let covered = query.len().min(name.len()) as f64 / name.len() as f64;
+    matched * (0.85 + 0.15 * covered)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cards::Card;
+    use crate::search::fuzzy::NameIndex;
+
+    fn index() -> NameIndex {
+        NameIndex::from_names(vec![
+            "Lightning Bolt",
+            "Lightning Helix",
+            "Chain Lightning",
+            "Jace, the Mind Sculptor",
+            "Jace Beleren",
+            "Lim-Dûl the Necromancer",
+            "Æther Vial",
+            "Urza's Tower",
+        ])
+    }
+
+    #[test]
+    fn resolve_misspelled() {
+        let index = index();
+        assert_eq!(index.resolve("lightnig bolt"), Some("Lightning Bolt"));
+        assert_eq!(index.resolve("Bolt Lightning"), Some("Lightning Bolt"));
+        assert_eq!(
+            index.resolve("jace mind sculptor"),
+            Some("Jace, the Mind Sculptor")
+        );
+        assert_eq!(
+            index.resolve("lim dul the necromancer"),
+            Some("Lim-Dûl the Necromancer")
+        );
+        assert_eq!(index.resolve("aether vial"), Some("Æther Vial"));
+        assert_eq!(index.resolve("urzas tower"), Some("Urza's Tower"));
+        assert_eq!(index.resolve("jace"), None);
+        assert_eq!(index.resolve("counterspell"), None);
+    }
+
+    #[test]
+    fn did_you_mean() {
+        let index = index();
+        assert!(index.did_you_mean("Lightning Bolt", 3).is_empty());
+        let suggestions = index.did_you_mean("jace", 2);
+        assert_eq!(suggestions.len(), 2);
+        assert!(suggestions.iter().all(|name| name.starts_with("Jace")));
+        assert!(index.did_you_mean("xyzzy", 3).is_empty());
+    }
+
+    #[test]
+    fn split_cards() {
+        let face = |name: &str, number: &str| Card {
+            name: name.to_string(),
+            layout: "split".to_string(),
+            number: number.to_string(),
+            names: Some(
+                vec!["Fire".to_string(), "Ice".to_string()]
+                    .into_iter()
+                    .collect(),
+            ),
+            ..Default::default()
+        };
+        let index = NameIndex::from_cards(vec![face("Ice", "128b"), face("Fire", "128a")]);
+        assert_eq!(index.len(), 3);
+        assert_eq!(index.resolve("fire // ice"), Some("Fire // Ice"));
+        assert_eq!(index.resolve("fire ice"), Some("Fire // Ice"));
+        assert_eq!(index.resolve("ice"), Some("Ice"));
+    }
+}
@@ -0,0 +1,114 @@
+//! Search cards held locally (e.g.: in an `offline::Store` or from a crawl).
+//!
+//! - `autocomplete` completes card names as they are typed;
+//! - `fulltext` searches the rules text, rulings and foreign texts of the cards;
+//! - `fuzzy` resolves misspelled card names and suggests "did you mean" candidates.
+//!
+//! The searches compare *folded* text: lowercase, without diacritics ("Lim-Dûl" is "lim dul", "Æther" is "aether")
+//! and with punctuation turned into spaces ("Fire // Ice" is "fire ice", "Urza's" is "urzas").
+pub mod autocomplete;
+pub mod fulltext;
+pub mod fuzzy;
+
+// Fold a single character, pushing its replacement into `out`
+fn fold_char(c: char, out: &mut String) {
+    let folded = match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'À' | 'Á' | 'Â' | 'Ã' | 'Ä' | 'Å' => "a",
+        'æ' | 'Æ' => "ae",
+        'ç' | 'Ç' => "c",
+        'è' | 'é' | 'ê' | 'ë' | 'È' | 'É' | 'Ê' | 'Ë' => "e",
+        'ì' | 'í' | 'î' | 'ï' | 'Ì' | 'Í' | 'Î' | 'Ï' => "i",
+        'ñ' | 'Ñ' => "n",
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'Ò' | 'Ó' | 'Ô' | 'Õ' | 'Ö' | 'Ø' => "o",
+        'œ' | 'Œ' => "oe",
+        'ù' | 'ú' | 'û' | 'ü' | 'Ù' | 'Ú' | 'Û' | 'Ü' => "u",
+        'ý' | 'ÿ' | 'Ý' => "y",
+        'ß' => "ss",
+        // Apostrophes join the word ("Urza's"), other punctuation separates words
+        '\'' | '’' => "",
+        c if c.is_alphanumeric() => {
+            out.extend(c.to_lowercase());
+            return;
+        }
+        _ => " ",
+    };
+    out.push_str(folded);
+}
+
+/// Folds text for comparison: lowercase, without diacritics, punctuation as single spaces.
+///
+/// # Example
+/// ```rust
+/// use mtgsdk::search;
+/// assert_eq!(search::fold("Lim-Dûl's Vault"), "lim duls vault");
+/// assert_eq!(search::fold("Fire // Ice"), "fire ice");
+/// ```
+pub fn fold(text: &str) -> String {
+    let mut folded = String::with_capacity(text.len());
+    for c in text.chars() {
+        fold_char(c, &mut folded);
+    }
+    folded.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+// Words of the folded text
+pub(crate) fn tokens(text: &str) -> Vec<String> {
+    fold(text)
+        .split(' ')
+        .filter(|t| !t.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+// Edit distance counting insertions, deletions, substitutions and swaps of adjacent characters
+pub(crate) fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut rows = vec![vec![0; b.len() + 1]; a.len() + 1];
+
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in rows[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            let mut best = (rows[i - 1][j] + 1)
+                .min(rows[i][j - 1] + 1)
+                .min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                best = best.min(rows[i - 2][j - 2] + 1);
+            }
+            rows[i][j] = best;
+        }
+    }
+    rows[a.len()][b.len()]
+}
+
+// Similarity between 0 (nothing in common) and 1 (same text), from the edit distance
+pub(crate) fn similarity(a: &str, b: &str) -> f64 {
+    let longest = a.chars().count().max(b.chars().count());
+    if longest == 0 {
+        return 1.0;
+    }
+    1.0 - edit_distance(a, b) as f64 / longest as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::search;
+
+    #[test]
+    fn fold_and_distance() {
+        assert_eq!(search::fold("  Æther   Vial "), "aether vial");
+        assert_eq!(
+            search::fold("Jace, the Mind Sculptor"),
+            "jace the mind sculptor"
+        );
+        assert_eq!(search::edit_distance("lightnig", "lightning"), 1);
+        assert_eq!(search::edit_distance("lihgtning", "lightning"), 1);
+        assert_eq!(search::edit_distance("", "bolt"), 4);
+    }
+}
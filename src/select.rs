@@ -0,0 +1,448 @@
+//! Filter and sort cards already fetched (e.g.: the results of `cards::filter()`, an `offline::Store`).
+//!
+//! The API has no negation nor numeric ranges, and `orderBy` takes a single field. `CardFilter` combines
+//! conditions with `and()`, `or()` and `not()`; `CardSort` orders by many keys in turn.
+//!
+//! # Example
+//! ```rust
+//! use mtgsdk::cards::{Card, Rarity};
+//! use mtgsdk::select::{CardFilter, CardSort, SortKey};
+//!
+//! let card = |name: &str, cmc: f64, rarity: &str| Card {
+//!     name: name.to_string(),
+//!     cmc,
+//!     rarity: rarity.to_string(),
+//!     ..Default::default()
+//! };
+//! let cards = vec![card("Shock", 1.0, "Common"), card("Bolt", 1.0, "Common"), card("Titan", 6.0, "Mythic Rare")];
+//!
+//! let cheap = CardFilter::cmc().le(3.0).and(CardFilter::rarity().ge(Rarity::Rare).not());
+//! let sort = CardSort::new().by(SortKey::Cmc).by(SortKey::Name);
+//! let names: Vec<String> = sort.sorted(cheap.apply(cards)).into_iter().map(|c| c.name).collect();
+//! assert_eq!(names, vec!["Bolt", "Shock"]);
+//! ```
+#![allow(dead_code)]
+use crate::cards::{color_name, color_symbol, Card, Rarity};
+use crate::date::Date;
+use crate::query::Query;
+use crate::stats::StatValue;
+use futures::future;
+use futures::stream::{Stream, StreamExt, TryStreamExt};
+use std::cmp::Ordering;
+use std::fmt;
+use std::sync::Arc;
+
+/// Condition on a card, combinable with `and()`, `or()` and `not()`.
+#[derive(Clone)]
+pub struct CardFilter(Arc<dyn Fn(&Card) -> bool + Send + Sync>);
+
+impl fmt::Debug for CardFilter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("CardFilter")
+    }
+}
+
+impl CardFilter {
+    /// Filter from any condition.
+    pub fn new<F>(condition: F) -> Self
+    where
+        F: Fn(&Card) -> bool + Send + Sync + 'static,
+    {
+        CardFilter(Arc::new(condition))
+    }
+
+    /// Matches every card.
+    pub fn any() -> Self {
+        CardFilter::new(|_| true)
+    }
+
+    pub fn and(self, other: CardFilter) -> Self {
+        CardFilter::new(move |card| self.matches(card) && other.matches(card))
+    }
+
+    pub fn or(self, other: CardFilter) -> Self {
+        CardFilter::new(move |card| self.matches(card) || other.matches(card))
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn not(self) -> Self {
+        CardFilter::new(move |card| !self.matches(card))
+    }
+
+    /// Converted mana cost.
+    pub fn cmc() -> Field<f64> {
+        Field::new(|card| Some(card.cmc))
+    }
+
+    /// Power (e.g.: `CardFilter::power().ge(StatValue::from(4))`); a variable counts as 0 (see `StatValue`).
+    pub fn power() -> Field<StatValue> {
+        Field::new(Card::power_value)
+    }
+
+    /// Toughness; a variable counts as 0 (see `StatValue`).
+    pub fn toughness() -> Field<StatValue> {
+        Field::new(Card::toughness_value)
+    }
+
+    /// Loyalty; a variable counts as 0 (see `StatValue`).
+    pub fn loyalty() -> Field<StatValue> {
+        Field::new(Card::loyalty_value)
+    }
+
+    /// Rarity; other rarities (e.g.: "Basic Land") match no comparison.
+    pub fn rarity() -> Field<Rarity> {
+        Field::new(|card| Rarity::parse(&card.rarity))
+    }
+
+    /// Release date of the printing; cards without one match no comparison.
+    pub fn release_date() -> Field<Date> {
+        Field::new(|card| card.release_date)
+    }
+
+    pub fn matches(&self, card: &Card) -> bool {
+        (self.0)(card)
+    }
+
+    /// Keeps the matching cards.
+    pub fn apply(&self, cards: Vec<Card>) -> Vec<Card> {
+        cards
+            .into_iter()
+            .filter(|card| self.matches(card))
+            .collect()
+    }
+
+    /// Keeps the matching cards of a stream (e.g.: `cards::Where::stream()`), letting the errors through.
+    pub fn stream<S, E>(&self, cards: S) -> impl Stream<Item = Result<Card, E>>
+    where
+        S: Stream<Item = Result<Card, E>>,
+    {
+        let filter = self.clone();
+        cards.filter(move |card| {
+            future::ready(match card {
+                Ok(card) => filter.matches(card),
+                Err(_) => true,
+            })
+        })
+    }
+}
+
+impl From<Query> for CardFilter {
+    fn from(query: Query) -> Self {
+        CardFilter::new(move |card| query.matches(card))
+    }
+}
+
+impl std::ops::Not for CardFilter {
+    type Output = CardFilter;
+
+    fn not(self) -> CardFilter {
+        CardFilter::not(self)
+    }
+}
+
+/// A value of the cards to compare (see `CardFilter::cmc()` and others). Cards without the value never match.
+pub struct Field<T> {
+    get: fn(&Card) -> Option<T>,
+}
+
+impl<T> Field<T>
+where
+    T: PartialOrd + Send + Sync + 'static,
+{
+    fn new(get: fn(&Card) -> Option<T>) -> Self {
+        Field { get }
+    }
+
+    fn compare(self, value: T, accept: fn(Ordering) -> bool) -> CardFilter {
+        let get = self.get;
+        CardFilter::new(move |card| {
+            get(card)
+                .and_then(|v| v.partial_cmp(&value))
+                .is_some_and(accept)
+        })
+    }
+
+    pub fn eq(self, value: T) -> CardFilter {
+        self.compare(value, |o| o == Ordering::Equal)
+    }
+
+    pub fn ne(self, value: T) -> CardFilter {
+        self.compare(value, |o| o != Ordering::Equal)
+    }
+
+    pub fn lt(self, value: T) -> CardFilter {
+        self.compare(value, |o| o == Ordering::Less)
+    }
+
+    pub fn le(self, value: T) -> CardFilter {
+        self.compare(value, |o| o != Ordering::Greater)
+    }
+
+    pub fn gt(self, value: T) -> CardFilter {
+        self.compare(value, |o| o == Ordering::Greater)
+    }
+
+    pub fn ge(self, value: T) -> CardFilter {
+        self.compare(value, |o| o != Ordering::Less)
+    }
+
+    /// Between `low` and `high`, both included.
+    pub fn between(self, low: T, high: T) -> CardFilter {
+        let get = self.get;
+        CardFilter::new(move |card| get(card).is_some_and(|v| low <= v && v <= high))
+    }
+}
+
+/// Key to sort cards by.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SortKey {
+    Name,
+    Cmc,
+    /// White, blue, black, red, green, then multicolor, then colorless.
+    Color,
+    Power,
+    Toughness,
+    Rarity,
+    ReleaseDate,
+    Set,
+    /// Collector number, numerically (e.g.: "9" before "10", "10a" before "10b").
+    Number,
+}
+
+/// Order of each key.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    Ascending,
+    Descending,
+}
+
+/// Sorts cards by many keys in turn (e.g.: color, then cmc, then name).
+///
+/// Cards without a value for a key (e.g.: no power) come last, whatever the direction.
+/// Cards equal on every key keep their order.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CardSort {
+    keys: Vec<(SortKey, Direction)>,
+}
+
+impl CardSort {
+    pub fn new() -> Self {
+        CardSort::default()
+    }
+
+    /// Adds a key, in ascending order.
+    pub fn by(self, key: SortKey) -> Self {
+        self.then(key, Direction::Ascending)
+    }
+
+    /// Adds a key, in descending order.
+    pub fn by_desc(self, key: SortKey) -> Self {
+        self.then(key, Direction::Descending)
+    }
+
+    /// Adds a key in the given order.
+    pub fn then(mut self, key: SortKey, direction: Direction) -> Self {
+        self.keys.push((key, direction));
+        self
+    }
+
+    pub fn compare(&self, a: &Card, b: &Card) -> Ordering {
+        self.keys
+            .iter()
+            .map(|&(key, direction)| compare_by(key, direction, a, b))
+            .find(|ordering| *ordering != Ordering::Equal)
+            .unwrap_or(Ordering::Equal)
+    }
+
+    pub fn sort(&self, cards: &mut [Card]) {
+        cards.sort_by(|a, b| self.compare(a, b));
+    }
+
+    pub fn sorted(&self, mut cards: Vec<Card>) -> Vec<Card> {
+        self.sort(&mut cards);
+        cards
+    }
+
+    /// Collects a stream of cards (e.g.: `cards::Where::stream()`) and sorts it.
+    ///
+    /// # Errors
+    /// If the stream yields an error, it will return it.
+    pub async fn sorted_stream<S, E>(&self, cards: S) -> Result<Vec<Card>, E>
+    where
+        S: Stream<Item = Result<Card, E>>,
+    {
+        let cards: Vec<Card> = cards.try_collect().await?;
+        Ok(self.sorted(cards))
+    }
+}
+
+fn compare_by(key: SortKey, direction: Direction, a: &Card, b: &Card) -> Ordering {
+    match key {
+        SortKey::Name => by(Some(&a.name), Some(&b.name), direction),
+        SortKey::Cmc => by(Some(a.cmc), Some(b.cmc), direction),
+        SortKey::Color => by(Some(color_key(a)), Some(color_key(b)), direction),
+        SortKey::Power => by(a.power_value(), b.power_value(), direction),
+        SortKey::Toughness => by(a.toughness_value(), b.toughness_value(), direction),
+        SortKey::Rarity => by(
+            Rarity::parse(&a.rarity),
+            Rarity::parse(&b.rarity),
+            direction,
+        ),
+        SortKey::ReleaseDate => by(a.release_date.as_ref(), b.release_date.as_ref(), direction),
+        SortKey::Set => by(Some(&a.set_field), Some(&b.set_field), direction),
+        SortKey::Number => by(
+            Some(number_key(&a.number)),
+            Some(number_key(&b.number)),
+            direction,
+        ),
+    }
+}
+
+// Missing values last, whatever the direction
+fn by<T: PartialOrd>(a: Option<T>, b: Option<T>, direction: Direction) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => {
+            let ordering = a.partial_cmp(&b).unwrap_or(Ordering::Equal);
+            match direction {
+                Direction::Ascending => ordering,
+                Direction::Descending => ordering.reverse(),
+            }
+        }
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+const WUBRG: [&str; 5] = ["W", "U", "B", "R", "G"];
+
+// Mono colors in WUBRG order, then multicolor (fewer colors first), then colorless
+fn color_key(card: &Card) -> (usize, usize, Vec<usize>) {
+    let mut positions: Vec<usize> = card
+        .colors
+        .iter()
+        .flatten()
+        .filter_map(|c| color_symbol(c).or_else(|| color_name(c).and_then(color_symbol)))
+        .filter_map(|symbol| WUBRG.iter().position(|s| *s == symbol))
+        .collect();
+    positions.sort_unstable();
+    positions.dedup();
+    match positions.len() {
+        0 => (2, 0, positions),
+        1 => (0, 0, positions),
+        n => (1, n, positions),
+    }
+}
+
+// Collector numbers: digits as a number, then the rest (e.g.: "10a" is (10, "a"))
+fn number_key(number: &str) -> (u64, String) {
+    let digits: String = number.chars().take_while(char::is_ascii_digit).collect();
+    let rest = number[digits.len()..].to_string();
+    (digits.parse().unwrap_or(u64::MAX), rest)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cards::{Card, Rarity};
+    use crate::fixtures::{self, strings};
+    use crate::select::{CardFilter, CardSort, SortKey};
+    use crate::stats::StatValue;
+    use futures::executor::block_on;
+    use futures::stream::{self, StreamExt};
+
+    fn card(name: &str, colors: &[&str], cmc: f64, power: Option<&str>) -> Card {
+        Card {
+            colors: Some(strings(colors)),
+            cmc,
+            power: power.map(String::from),
+            rarity: "Rare".to_string(),
+            ..fixtures::card(name)
+        }
+    }
+
+    fn cards() -> Vec<Card> {
+        vec![
+            card("Ornithopter", &[], 0.0, Some("0")),
+            card("Lightning Helix", &["Red", "White"], 2.0, None),
+            card("Shivan Dragon", &["Red"], 6.0, Some("5")),
+            card("Tarmogoyf", &["Green"], 2.0, Some("*")),
+            card("Serra Angel", &["White"], 5.0, Some("4")),
+            card("Goblin Guide", &["Red"], 1.0, Some("2")),
+        ]
+    }
+
+    fn names(cards: &[Card]) -> Vec<&str> {
+        cards.iter().map(|c| c.name.as_str()).collect()
+    }
+
+    #[test]
+    fn filter_combinators() {
+        let creatures = CardFilter::power().ge(StatValue::from(1));
+        let cheap = CardFilter::cmc().between(1.0, 3.0);
+        let filtered = creatures.clone().and(cheap.clone()).apply(cards());
+        assert_eq!(names(&filtered), vec!["Goblin Guide"]);
+
+        let filtered = (!creatures).or(CardFilter::cmc().gt(5.0)).apply(cards());
+        assert_eq!(
+            names(&filtered),
+            vec![
+                "Ornithopter",
+                "Lightning Helix",
+                "Shivan Dragon",
+                "Tarmogoyf"
+            ]
+        );
+
+        let mythic = CardFilter::rarity().eq(Rarity::Mythic);
+        assert!(mythic.apply(cards()).is_empty());
+
+        let stream = stream::iter(cards().into_iter().map(Ok::<Card, ()>));
+        let found: Vec<_> = block_on(cheap.stream(stream).collect());
+        assert_eq!(found.len(), 3);
+    }
+
+    #[test]
+    fn sort_multi_key() {
+        let sort = CardSort::new()
+            .by(SortKey::Color)
+            .by(SortKey::Cmc)
+            .by(SortKey::Name);
+        assert_eq!(
+            names(&sort.sorted(cards())),
+            vec![
+                "Serra Angel",
+                "Goblin Guide",
+                "Shivan Dragon",
+                "Tarmogoyf",
+                "Lightning Helix",
+                "Ornithopter"
+            ]
+        );
+
+        let sort = CardSort::new().by_desc(SortKey::Power).by(SortKey::Name);
+        let stream = stream::iter(cards().into_iter().map(Ok::<Card, ()>));
+        let sorted = block_on(sort.sorted_stream(stream)).unwrap();
+        assert_eq!(
+            names(&sorted),
+            vec![
+                "Shivan Dragon",
+                "Serra Angel",
+                "Goblin Guide",
+                "Tarmogoyf",
+                "Ornithopter",
+                "Lightning Helix"
+            ]
+        );
+
+        let mut numbered: Vec<Card> = ["10b", "9", "10a", "★"]
+            .iter()
+            .map(|n| Card {
+                number: n.to_string(),
+                ..Default::default()
+            })
+            .collect();
+        CardSort::new().by(SortKey::Number).sort(&mut numbered);
+        let numbers: Vec<&str> = numbered.iter().map(|c| c.number.as_str()).collect();
+        assert_eq!(numbers, vec!["9", "10a", "10b", "★"]);
+    }
+}
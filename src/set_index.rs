@@ -0,0 +1,308 @@
+//! Sets grouped into blocks and families, with their old codes.
+//!
+//! `sets::filter().block()` finds the sets of a block, one call at a time. A `SetIndex` holds all the sets
+//! (chronologically) and navigates them: the blocks (`Set::block`), the families (core sets, masters sets,
+//! commander products, ...: see `Family`), and the sets sharing a parent code (e.g.: the promos "PKTK" and the tokens
+//! "TKTK" of Khans of Tarkir "KTK").
+//!
+//! It also resolves the other codes of a set (`Set::old_code`, `Set::gatherer_code`, `Set::magic_cards_info_code`)
+//! to its `Set::code` (e.g.: "4E" is "4ED").
+//!
+//! # Example
+//! ```rust
+//! use mtgsdk::set_index::SetIndex;
+//! async {
+//!     let index = SetIndex::fetch().await.unwrap();
+//!     let block = index.block_of("BOK").unwrap();
+//!     assert_eq!(block.name, "Kamigawa");
+//!     assert_eq!(block.sets.len(), 3);
+//!     assert_eq!(index.resolve("4E").unwrap(), "4ED");
+//! };
+//! ```
+#![allow(dead_code)]
+use crate::client::Client;
+use crate::ids::SetCode;
+use crate::sets::{self, Set};
+use http::StatusCode;
+use std::collections::HashMap;
+
+/// Family of a set, from its `Set::type_field`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Family {
+    /// "core" (e.g.: Magic 2015).
+    Core,
+    /// "expansion" (e.g.: Innistrad).
+    Expansion,
+    /// "masters" (e.g.: Modern Masters).
+    Masters,
+    /// "commander" (e.g.: Commander 2014).
+    Commander,
+    /// Any other type (e.g.: "promo", "duel_deck"), in lowercase.
+    Other(String),
+}
+
+impl Family {
+    pub fn of(set: &Set) -> Family {
+        let type_field = set.type_field.to_lowercase();
+        match type_field.as_str() {
+            "core" => Family::Core,
+            "expansion" => Family::Expansion,
+            "masters" => Family::Masters,
+            "commander" => Family::Commander,
+            _ => Family::Other(type_field),
+        }
+    }
+}
+
+/// Sets of a block, chronologically.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Block<'a> {
+    pub name: &'a str,
+    pub sets: Vec<&'a Set>,
+}
+
+/// Sets ordered chronologically, with their blocks, families and codes.
+#[derive(Clone, Debug, Default)]
+pub struct SetIndex {
+    sets: Vec<Set>,
+    // Position of each set by code, then by its other codes
+    codes: HashMap<String, usize>,
+    aliases: HashMap<String, usize>,
+    parents: HashMap<usize, usize>,
+}
+
+impl SetIndex {
+    pub fn new<I: IntoIterator<Item = Set>>(sets: I) -> Self {
+        let mut sets: Vec<Set> = sets.into_iter().collect();
+        sets.sort_by(sets::by_release);
+
+        let mut codes = HashMap::new();
+        for (at, set) in sets.iter().enumerate() {
+            codes.entry(set.code.to_uppercase()).or_insert(at);
+        }
+
+        let mut aliases = HashMap::new();
+        for (at, set) in sets.iter().enumerate() {
+            let others = [
+                &set.old_code,
+                &set.gatherer_code,
+                &set.magic_cards_info_code,
+            ];
+            for alias in others.iter().filter_map(|code| code.as_deref()) {
+                let alias = alias.trim().to_uppercase();
+                if !alias.is_empty() && !codes.contains_key(&alias) {
+                    aliases.entry(alias).or_insert(at);
+                }
+            }
+        }
+
+        // "PKTK" and "TKTK" (promos and tokens) belong to "KTK"
+        let mut parents = HashMap::new();
+        for (at, set) in sets.iter().enumerate() {
+            let code = set.code.to_uppercase();
+            let is_child = code.len() >= 4 && (code.starts_with('P') || code.starts_with('T'));
+            let family = Family::of(set);
+            if is_child && family != Family::Core && family != Family::Expansion {
+                if let Some(&parent) = codes.get(&code[1..]) {
+                    parents.insert(at, parent);
+                }
+            }
+        }
+
+        SetIndex {
+            sets,
+            codes,
+            aliases,
+            parents,
+        }
+    }
+
+    /// Function to build the index from every set (see `sets::all()`).
+    ///
+    /// # Errors
+    /// If the call fails, it will return a `Err(StatusCode)`.
+    /// To see the possible return values, check the [API docs](https://docs.magicthegathering.io/#documentationerrors).
+    pub async fn fetch() -> Result<SetIndex, StatusCode> {
+        SetIndex::fetch_with(&Client::default()).await
+    }
+
+    /// Function to build the index from every set with the given `Client` (see `client`).
+    ///
+    /// # Errors
+    /// If the call fails, it will return a `Err(StatusCode)`.
+    /// To see the possible return values, check the [API docs](https://docs.magicthegathering.io/#documentationerrors).
+    pub async fn fetch_with(client: &Client) -> Result<SetIndex, StatusCode> {
+        Ok(SetIndex::new(sets::all_with(client).await?))
+    }
+
+    pub fn len(&self) -> usize {
+        self.sets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sets.is_empty()
+    }
+
+    /// Every set, chronologically.
+    pub fn sets(&self) -> &[Set] {
+        &self.sets
+    }
+
+    fn position(&self, code: &str) -> Option<usize> {
+        let code = code.trim().to_uppercase();
+        self.codes
+            .get(&code)
+            .or_else(|| self.aliases.get(&code))
+            .copied()
+    }
+
+    /// The set with the given code or one of its other codes (case insensitive).
+    pub fn get(&self, code: &str) -> Option<&Set> {
+        self.position(code).map(|at| &self.sets[at])
+    }
+
+    /// `Set::code` of the set with the given code or one of its other codes (e.g.: "4E" is "4ED").
+    pub fn resolve(&self, code: &str) -> Option<&SetCode> {
+        self.get(code).map(|set| &set.code)
+    }
+
+    /// Every block, ordered by its first set.
+    pub fn blocks(&self) -> Vec<Block<'_>> {
+        let mut blocks: Vec<Block<'_>> = Vec::new();
+        for set in &self.sets {
+            let name = match set.block.as_deref() {
+                Some(name) => name,
+                None => continue,
+            };
+            match blocks
+                .iter_mut()
+                .find(|block| block.name.eq_ignore_ascii_case(name))
+            {
+                Some(block) => block.sets.push(set),
+                None => blocks.push(Block {
+                    name,
+                    sets: vec![set],
+                }),
+            }
+        }
+        blocks
+    }
+
+    /// The block with the given name (case insensitive).
+    pub fn block(&self, name: &str) -> Option<Block<'_>> {
+        self.blocks()
+            .into_iter()
+            .find(|block| block.name.eq_ignore_ascii_case(name))
+    }
+
+    /// The block of the set with the given code (`None` if the set is not in a block).
+    pub fn block_of(&self, code: &str) -> Option<Block<'_>> {
+        let name = self.get(code)?.block.as_deref()?;
+        self.block(name)
+    }
+
+    /// Sets of the given family, chronologically.
+    pub fn family(&self, family: &Family) -> Vec<&Set> {
+        self.sets
+            .iter()
+            .filter(|set| Family::of(set) == *family)
+            .collect()
+    }
+
+    /// The set whose code the given set shares (e.g.: "KTK" for "PKTK").
+    pub fn parent(&self, code: &str) -> Option<&Set> {
+        let at = self.position(code)?;
+        self.parents.get(&at).map(|&parent| &self.sets[parent])
+    }
+
+    /// Sets sharing the code of the given set (e.g.: "PKTK" and "TKTK" for "KTK"), chronologically.
+    pub fn children(&self, code: &str) -> Vec<&Set> {
+        let at = match self.position(code) {
+            Some(at) => at,
+            None => return Vec::new(),
+        };
+        let mut children: Vec<usize> = self
+            .parents
+            .iter()
+            .filter(|(_, &parent)| parent == at)
+            .map(|(&child, _)| child)
+            .collect();
+        children.sort_unstable();
+        children
+            .into_iter()
+            .map(|child| &self.sets[child])
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::{codes, set};
+    use crate::set_index::{Family, SetIndex};
+
+    fn index() -> SetIndex {
+        let mut fourth = set("4ED", "core", None, "1995-04-01");
+        fourth.old_code = Some("4E".to_string());
+        fourth.gatherer_code = Some("4E".to_string());
+        let mut visions = set("VIS", "expansion", Some("Mirage"), "1997-02-03");
+        visions.gatherer_code = Some("VI".to_string());
+        visions.magic_cards_info_code = Some("vi".to_string());
+        let mut mirage = set("MIR", "expansion", Some("Mirage"), "1996-10-08");
+        // An alias does not hide a set's own code
+        mirage.old_code = Some("VIS".to_string());
+
+        SetIndex::new(vec![
+            set("FRF", "expansion", Some("Khans of Tarkir"), "2015-01-23"),
+            set("KTK", "expansion", Some("Khans of Tarkir"), "2014-09-26"),
+            set("TKTK", "token", Some("Khans of Tarkir"), "2014-09-26"),
+            set("PKTK", "promo", None, "2014-09-20"),
+            set("C14", "commander", None, "2014-11-07"),
+            set("MM2", "masters", None, "2015-05-22"),
+            set("WTH", "expansion", Some("Mirage"), "1997-06-09"),
+            visions,
+            mirage,
+            fourth,
+        ])
+    }
+
+    #[test]
+    fn blocks_and_families() {
+        let index = index();
+        assert_eq!(index.len(), 10);
+        assert_eq!(index.sets()[0].code, "4ED");
+
+        let blocks = index.blocks();
+        let names: Vec<&str> = blocks.iter().map(|block| block.name).collect();
+        assert_eq!(names, vec!["Mirage", "Khans of Tarkir"]);
+        assert_eq!(codes(&blocks[0].sets), vec!["MIR", "VIS", "WTH"]);
+
+        let block = index.block_of("frf").unwrap();
+        assert_eq!(codes(&block.sets), vec!["KTK", "TKTK", "FRF"]);
+        assert!(index.block_of("C14").is_none());
+        assert!(index.block("mirage").is_some());
+
+        assert_eq!(codes(&index.family(&Family::Core)), vec!["4ED"]);
+        assert_eq!(codes(&index.family(&Family::Masters)), vec!["MM2"]);
+        assert_eq!(codes(&index.family(&Family::Commander)), vec!["C14"]);
+        assert_eq!(
+            codes(&index.family(&Family::Other("promo".to_string()))),
+            vec!["PKTK"]
+        );
+
+        assert_eq!(codes(&index.children("KTK")), vec!["PKTK", "TKTK"]);
+        assert_eq!(index.parent("pktk").unwrap().code, "KTK");
+        assert!(index.parent("KTK").is_none());
+        assert!(index.children("FRF").is_empty());
+    }
+
+    #[test]
+    fn resolve_aliases() {
+        let index = index();
+        assert_eq!(index.resolve("4E").unwrap(), "4ED");
+        assert_eq!(index.resolve("4ed").unwrap(), "4ED");
+        assert_eq!(index.resolve("vi").unwrap(), "VIS");
+        assert_eq!(index.resolve("VIS").unwrap(), "VIS");
+        assert!(index.resolve("XYZ").is_none());
+        assert_eq!(index.get("VI").unwrap().block.as_deref(), Some("Mirage"));
+    }
+}
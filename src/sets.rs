@@ -3,9 +3,13 @@
 //! Alongside `cards`, `sets` is one of the calls that allow the `find()` method as well as specific filters.
 //! For a complete list of the paremeters available for the filters, check [API docs](https://docs.magicthegathering.io/#api_v1sets_list).
 #![allow(dead_code)]
+use crate::client::Client;
+use crate::date::{self, Date};
+use crate::ids::SetCode;
 use crate::query_builder;
-use reqwest::StatusCode;
+use http::StatusCode;
 use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
 
 /// Structure to deserialize sets' JSON.
 ///
@@ -13,13 +17,14 @@ use serde::{Deserialize, Serialize};
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Set {
-    pub code: String,
+    pub code: SetCode,
     pub name: String,
     #[serde(rename = "type")]
     pub type_field: String,
     #[serde(default)]
     pub booster: Vec<Booster>,
-    pub release_date: String,
+    #[serde(default, deserialize_with = "date::lenient")]
+    pub release_date: Option<Date>,
     pub block: Option<String>,
     pub online_only: Option<bool>,
     pub gatherer_code: Option<String>,
@@ -42,13 +47,13 @@ pub enum Booster {
 #[doc(hidden)]
 #[derive(Clone, Debug, Deserialize)]
 pub struct RootAll {
-    sets: Vec<Set>,
+    pub(crate) sets: Vec<Set>,
 }
 
 #[doc(hidden)]
 #[derive(Clone, Debug, Deserialize)]
 pub struct RootFind {
-    set: Set,
+    pub(crate) set: Set,
 }
 
 /// Function to get all sets.
@@ -68,7 +73,16 @@ pub struct RootFind {
 /// If the call fails, it will return a `Err(StatusCode)`.
 /// To see the possible return values, check the [API docs](https://docs.magicthegathering.io/#documentationerrors).
 pub async fn all() -> Result<Vec<Set>, StatusCode> {
-    let sets: Result<RootAll, StatusCode> = query_builder::all("sets").await;
+    all_with(&Client::default()).await
+}
+
+/// Function to get all sets with the given `Client` (see `client`).
+///
+/// # Errors
+/// If the call fails, it will return a `Err(StatusCode)`.
+/// To see the possible return values, check the [API docs](https://docs.magicthegathering.io/#documentationerrors).
+pub async fn all_with(client: &Client) -> Result<Vec<Set>, StatusCode> {
+    let sets: Result<RootAll, StatusCode> = query_builder::all(client, "sets").await;
 
     match sets {
         Ok(t) => Ok(t.sets),
@@ -76,7 +90,7 @@ pub async fn all() -> Result<Vec<Set>, StatusCode> {
     }
 }
 
-/// Function to get a single set.
+/// Function to get a single set, by its code (a `SetCode` or a `&str`, see `ids`).
 ///
 /// # Example
 /// ```rust
@@ -90,8 +104,30 @@ pub async fn all() -> Result<Vec<Set>, StatusCode> {
 /// # Errors
 /// If the call fails, it will return a `Err(StatusCode)`.
 /// To see the possible return values, check the [API docs](https://docs.magicthegathering.io/#documentationerrors).
-pub async fn find(id: &str) -> Result<Set, StatusCode> {
-    let sets: Result<RootFind, StatusCode> = query_builder::find("sets", id).await;
+pub async fn find<C: Into<SetCode>>(code: C) -> Result<Set, StatusCode> {
+    find_with(&Client::default(), code).await
+}
+
+/// Function to get a single set with the given `Client` (see `client`).
+///
+/// # Example
+/// ```rust
+/// use mtgsdk::client::Client;
+/// use mtgsdk::sets;
+/// async {
+///    let client = Client::default();
+///    let sets = sets::find_with(&client, "dom").await;
+///    assert_eq!(sets.unwrap().name, "Dominaria");
+/// };
+///```
+///
+/// # Errors
+/// If the call fails, it will return a `Err(StatusCode)`.
+/// To see the possible return values, check the [API docs](https://docs.magicthegathering.io/#documentationerrors).
+pub async fn find_with<C: Into<SetCode>>(client: &Client, code: C) -> Result<Set, StatusCode> {
+    let code = code.into();
+    let sets: Result<RootFind, StatusCode> =
+        query_builder::find(client, "sets", code.as_str()).await;
 
     match sets {
         Ok(t) => Ok(t.set),
@@ -99,9 +135,75 @@ pub async fn find(id: &str) -> Result<Set, StatusCode> {
     }
 }
 
+/// Function to sort sets chronologically (sets released the same day by code).
+///
+/// # Example
+/// ```rust
+/// use mtgsdk::sets;
+/// async {
+///    let mut all = sets::all().await.unwrap();
+///    sets::sort_by_release(&mut all);
+///    assert_eq!(all[0].name, "Limited Edition Alpha");
+/// };
+///```
+pub fn sort_by_release(sets: &mut [Set]) {
+    sets.sort_by(by_release);
+}
+
+/// Function to get the sets released between two dates (both included), chronologically.
+///
+/// Sets without a release date are left out.
+pub fn released_between(sets: &[Set], from: Date, to: Date) -> Vec<&Set> {
+    let mut between: Vec<&Set> = sets
+        .iter()
+        .filter(|set| {
+            set.release_date
+                .is_some_and(|date| from <= date && date <= to)
+        })
+        .collect();
+    between.sort_by(|a, b| by_release(a, b));
+    between
+}
+
+/// Function to get the latest set of the given `type_field` (e.g.: "core", "expansion"), ignoring case.
+///
+/// Sets without a release date are left out.
+pub fn latest_of_type<'a>(sets: &'a [Set], type_field: &str) -> Option<&'a Set> {
+    sets.iter()
+        .filter(|set| set.release_date.is_some() && set.type_field.eq_ignore_ascii_case(type_field))
+        .max_by(|a, b| by_release(a, b))
+}
+
+/// Function to get the newest set on the given date, i.e.: the latest one released on that day or before
+/// (of the sets released the same day, the last by code). Sets without a release date are left out.
+///
+/// # Example
+/// ```rust
+/// use mtgsdk::date::Date;
+/// use mtgsdk::sets;
+/// async {
+///    let all = sets::all().await.unwrap();
+///    let newest = sets::newest_on(&all, Date::new(2011, 10, 1).unwrap());
+///    assert_eq!(newest.unwrap().name, "Innistrad");
+/// };
+///```
+pub fn newest_on(sets: &[Set], date: Date) -> Option<&Set> {
+    sets.iter()
+        .filter(|set| set.release_date.is_some_and(|released| released <= date))
+        .max_by(|a, b| by_release(a, b))
+}
+
+// Chronological order (sets without a release date last), then by code
+pub(crate) fn by_release(a: &Set, b: &Set) -> Ordering {
+    let key = |set: &Set| (set.release_date.is_none(), set.release_date);
+    key(a).cmp(&key(b)).then_with(|| a.code.cmp(&b.code))
+}
+
 #[doc(hidden)]
+#[derive(Clone, Debug)]
 pub struct Where<'a> {
     query: Vec<(&'a str, String)>,
+    client: Option<Client>,
 }
 
 /// Function to get all card matching the query filters.
@@ -141,10 +243,19 @@ pub struct Where<'a> {
 /// If the call fails, it will return a `Err(StatusCode)`.
 /// To see the possible return values, check https://docs.magicthegathering.io/#documentationerrors.
 pub fn filter<'a>() -> Where<'a> {
-    Where { query: Vec::new() }
+    Where {
+        query: Vec::new(),
+        client: None,
+    }
 }
 
 impl<'a> Where<'a> {
+    /// Makes the call with the given `Client` instead of the default one (see `client`).
+    pub fn client(mut self, client: &Client) -> Self {
+        self.client = Some(client.clone());
+        self
+    }
+
     pub fn name(mut self, input: &'a str) -> Self {
         self.query.push(("name", String::from(input)));
         self
@@ -165,15 +276,15 @@ impl<'a> Where<'a> {
         self
     }
 
-    pub async fn all(mut self) -> Result<Vec<Set>, StatusCode> {
-        let val = self.query.remove(0);
-        let mut filter = format!("?{}={}", val.0, val.1);
-
-        for (k, v) in self.query.into_iter() {
-            filter = format!("{}&{}={}", filter, k, v);
-        }
+    // Build the query string (?param=value&param=value)
+    pub(crate) fn query_string(&self) -> String {
+        query_builder::query_string(&self.query)
+    }
 
-        let sets: Result<RootAll, StatusCode> = query_builder::filter("sets", &filter).await;
+    pub async fn all(self) -> Result<Vec<Set>, StatusCode> {
+        let client = self.client.clone().unwrap_or_default();
+        let sets: Result<RootAll, StatusCode> =
+            query_builder::filter(&client, "sets", &self.query_string()).await;
 
         match sets {
             Ok(t) => Ok(t.sets),
@@ -181,3 +292,102 @@ impl<'a> Where<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::client::tests::Fake;
+    use crate::client::Client;
+    use crate::date::Date;
+    use crate::fixtures::{codes, set, set_code};
+    use crate::sets::{self, Set};
+    use futures::executor::block_on;
+    use std::sync::Arc;
+
+    #[test]
+    fn release_dates() {
+        let mut all = vec![
+            set("ISD", "expansion", None, "2011-09-30"),
+            set("M12", "core", None, "2011-07-15"),
+            set("DKA", "expansion", None, "2012-02-03"),
+            set("PD3", "premium_deck", None, "2011-09-30"),
+            set("M13", "core", None, "2012-07-13"),
+            // Not dated yet: left out of the chronology, sorted last
+            Set {
+                release_date: None,
+                ..set("MB1", "core", None, "2011-09-30")
+            },
+        ];
+        let date = |s: &str| s.parse::<Date>().unwrap();
+        let between = sets::released_between(&all, date("2011-07-15"), date("2012-02-03"));
+        assert_eq!(codes(&between), vec!["M12", "ISD", "PD3", "DKA"]);
+
+        assert_eq!(
+            sets::latest_of_type(&all, "Core").unwrap().code.to_string(),
+            "M13"
+        );
+        assert!(sets::latest_of_type(&all, "masters").is_none());
+
+        assert_eq!(
+            sets::newest_on(&all, date("2011-09-29"))
+                .unwrap()
+                .code
+                .to_string(),
+            "M12"
+        );
+        assert_eq!(
+            sets::newest_on(&all, date("2012-07-13"))
+                .unwrap()
+                .code
+                .to_string(),
+            "M13"
+        );
+        assert!(sets::newest_on(&all, date("2011-01-01")).is_none());
+
+        sets::sort_by_release(&mut all);
+        let sorted: Vec<&Set> = all.iter().collect();
+        assert_eq!(
+            codes(&sorted),
+            vec!["M12", "ISD", "PD3", "DKA", "M13", "MB1"]
+        );
+
+        let json =
+            r#"{"code":"ISD","name":"Innistrad","type":"expansion","releaseDate":"2011-09-30"}"#;
+        let set: Set = serde_json::from_str(json).unwrap();
+        assert_eq!(set.release_date, Some(date("2011-09-30")));
+        assert!(serde_json::to_string(&set)
+            .unwrap()
+            .contains(r#""releaseDate":"2011-09-30""#));
+        for json in &[
+            r#"{"code":"MB1","name":"Mystery Booster","type":"masters"}"#,
+            r#"{"code":"MB1","name":"Mystery Booster","type":"masters","releaseDate":"TBA"}"#,
+        ] {
+            let set: Set = serde_json::from_str(json).unwrap();
+            assert_eq!(set.release_date, None);
+        }
+    }
+
+    #[test]
+    fn find_by_code() {
+        let dom = Set {
+            name: "Dominaria".to_string(),
+            ..set("DOM", "expansion", None, "2018-04-27")
+        };
+        let body = serde_json::json!({ "set": dom }).to_string();
+        let fake = Arc::new(Fake::new(&[("/v1/sets/DOM", &body)]));
+        let client = Client::with_transport(fake.clone());
+        assert_eq!(
+            block_on(sets::find_with(&client, "dom")).unwrap().name,
+            "Dominaria"
+        );
+        let code = set_code("DOM");
+        assert_eq!(
+            block_on(sets::find_with(&client, &code)).unwrap().name,
+            "Dominaria"
+        );
+        assert_eq!(
+            block_on(sets::find_with(&client, code)).unwrap().name,
+            "Dominaria"
+        );
+        assert_eq!(*fake.requests.lock().unwrap(), vec!["/v1/sets/DOM"; 3]);
+    }
+}
@@ -0,0 +1,293 @@
+//! Typed power, toughness and loyalty (e.g.: "3", "*", "1+*", "-1", "X", "?", "∞").
+//!
+//! The API gives these values as text (`Card::power`, `Card::toughness`, `Card::loyalty`). `StatValue` reads them
+//! into a number and an optional variable part, so they can be compared: as in the game outside the battlefield,
+//! a variable counts as 0 (e.g.: "1+*" is 1).
+#![allow(dead_code)]
+use crate::cards::Card;
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+/// Error reading a `StatValue`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseStatError {
+    input: String,
+}
+
+impl fmt::Display for ParseStatError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid power, toughness or loyalty: {:?}", self.input)
+    }
+}
+
+impl std::error::Error for ParseStatError {}
+
+/// Variable part of a value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Variable {
+    /// `*`, defined by the card's text (e.g.: Tarmogoyf).
+    Star,
+    /// `X`, chosen when the card is cast or comes into play.
+    X,
+    /// `?`, unknown (e.g.: some Un-cards).
+    Unknown,
+}
+
+impl Variable {
+    fn symbol(self) -> &'static str {
+        match self {
+            Variable::Star => "*",
+            Variable::X => "X",
+            Variable::Unknown => "?",
+        }
+    }
+}
+
+/// Power, toughness or loyalty.
+///
+/// Values are ordered by their number (variables counting as 0, "∞" above every number),
+/// then plain numbers before the ones with a variable part.
+///
+/// # Example
+/// ```rust
+/// use mtgsdk::stats::{StatValue, Variable};
+///
+/// let value: StatValue = "1+*".parse().unwrap();
+/// assert_eq!(value.number(), 1.0);
+/// assert_eq!(value.variable(), Some(Variable::Star));
+/// assert!(value < StatValue::from(2));
+/// assert!("∞".parse::<StatValue>().unwrap() > StatValue::from(99));
+/// assert_eq!(value.to_string(), "1+*");
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub enum StatValue {
+    /// A number (e.g.: "3", "-1", "½").
+    Fixed(f64),
+    /// A number and a variable, added or subtracted (e.g.: "*" is `0 + *`, "7-*" is `7 - *`).
+    Variable {
+        base: f64,
+        variable: Variable,
+        negative: bool,
+    },
+    /// "∞".
+    Infinite,
+}
+
+impl StatValue {
+    /// Reads a value as given by the API.
+    ///
+    /// # Errors
+    /// If the text is not a value, it will return a `Err(ParseStatError)`.
+    pub fn parse(value: &str) -> Result<StatValue, ParseStatError> {
+        let error = || ParseStatError {
+            input: value.to_string(),
+        };
+        let text = value.trim();
+        if text == "∞" {
+            return Ok(StatValue::Infinite);
+        }
+        if let Some(variable) = variable(text) {
+            return Ok(StatValue::Variable {
+                base: 0.0,
+                variable,
+                negative: false,
+            });
+        }
+
+        // "1+*", "7-*": the variable is last, after the sign
+        if let Some(at) = text.rfind(['+', '-']).filter(|&at| at > 0) {
+            let (base, variable_part) = text.split_at(at);
+            let negative = variable_part.starts_with('-');
+            let variable = self::variable(&variable_part[1..]).ok_or_else(error)?;
+            let base = number(base).ok_or_else(error)?;
+            return Ok(StatValue::Variable {
+                base,
+                variable,
+                negative,
+            });
+        }
+
+        number(text).map(StatValue::Fixed).ok_or_else(error)
+    }
+
+    /// Numeric part (a variable counting as 0; "∞" is `f64::INFINITY`).
+    pub fn number(&self) -> f64 {
+        match self {
+            StatValue::Fixed(n) => *n,
+            StatValue::Variable { base, .. } => *base,
+            StatValue::Infinite => f64::INFINITY,
+        }
+    }
+
+    /// Variable part, if any.
+    pub fn variable(&self) -> Option<Variable> {
+        match self {
+            StatValue::Variable { variable, .. } => Some(*variable),
+            _ => None,
+        }
+    }
+
+    pub fn is_variable(&self) -> bool {
+        self.variable().is_some()
+    }
+
+    // Order after the number: plain, then each variable (added, then subtracted)
+    fn rank(&self) -> (u8, bool) {
+        match self {
+            StatValue::Fixed(_) | StatValue::Infinite => (0, false),
+            StatValue::Variable {
+                variable, negative, ..
+            } => (1 + *variable as u8, *negative),
+        }
+    }
+}
+
+fn variable(text: &str) -> Option<Variable> {
+    match text {
+        "*" => Some(Variable::Star),
+        "X" | "x" => Some(Variable::X),
+        "?" => Some(Variable::Unknown),
+        _ => None,
+    }
+}
+
+// Integers, decimals and halves (e.g.: "3", "-1", "2.5", "½", "3½")
+fn number(text: &str) -> Option<f64> {
+    let (text, half) = match text.strip_suffix('½') {
+        Some(rest) => (rest, 0.5),
+        None => (text, 0.0),
+    };
+    let n = match text {
+        "" if half > 0.0 => 0.0,
+        "-" if half > 0.0 => -0.0,
+        _ => text.parse::<f64>().ok().filter(|n| n.is_finite())?,
+    };
+    let n = if n.is_sign_negative() {
+        n - half
+    } else {
+        n + half
+    };
+    // -0.0 is 0
+    Some(n + 0.0)
+}
+
+impl fmt::Display for StatValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let number = |n: f64| {
+            if n.fract() == 0.0 {
+                format!("{}", n as i64)
+            } else {
+                format!("{}", n)
+            }
+        };
+        match self {
+            StatValue::Fixed(n) => f.write_str(&number(*n)),
+            StatValue::Infinite => f.write_str("∞"),
+            StatValue::Variable {
+                base,
+                variable,
+                negative,
+            } => {
+                if *base == 0.0 && !negative {
+                    f.write_str(variable.symbol())
+                } else {
+                    let sign = if *negative { '-' } else { '+' };
+                    write!(f, "{}{}{}", number(*base), sign, variable.symbol())
+                }
+            }
+        }
+    }
+}
+
+impl FromStr for StatValue {
+    type Err = ParseStatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        StatValue::parse(s)
+    }
+}
+
+impl From<i64> for StatValue {
+    fn from(n: i64) -> Self {
+        StatValue::Fixed(n as f64)
+    }
+}
+
+impl Ord for StatValue {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.number()
+            .total_cmp(&other.number())
+            .then_with(|| self.rank().cmp(&other.rank()))
+    }
+}
+
+impl PartialOrd for StatValue {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for StatValue {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for StatValue {}
+
+impl Card {
+    /// `power` as a `StatValue` (`None` if the card has no power or it cannot be read).
+    pub fn power_value(&self) -> Option<StatValue> {
+        self.power.as_deref()?.parse().ok()
+    }
+
+    /// `toughness` as a `StatValue` (`None` if the card has no toughness or it cannot be read).
+    pub fn toughness_value(&self) -> Option<StatValue> {
+        self.toughness.as_deref()?.parse().ok()
+    }
+
+    /// `loyalty` as a `StatValue` (`None` if the card has no loyalty or it cannot be read).
+    pub fn loyalty_value(&self) -> Option<StatValue> {
+        self.loyalty.as_deref()?.parse().ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::stats::{StatValue, Variable};
+
+    #[test]
+    fn parse_values() {
+        let parse = |s: &str| StatValue::parse(s).unwrap();
+        assert_eq!(parse("3"), StatValue::Fixed(3.0));
+        assert_eq!(parse("-1"), StatValue::Fixed(-1.0));
+        assert_eq!(parse("½").number(), 0.5);
+        assert_eq!(parse("3½").number(), 3.5);
+        assert_eq!(parse("2.5").number(), 2.5);
+        assert_eq!(parse("x").variable(), Some(Variable::X));
+        assert_eq!(parse("?").variable(), Some(Variable::Unknown));
+        assert_eq!(parse("7-*").number(), 7.0);
+        assert!(StatValue::parse("").is_err());
+        assert!(StatValue::parse("*²").is_err());
+        assert!(StatValue::parse("1+Y").is_err());
+        assert!(StatValue::parse("NaN").is_err());
+
+        for text in ["3", "-1", "*", "1+*", "7-*", "X", "?", "∞", "2.5"] {
+            assert_eq!(parse(text).to_string(), text);
+        }
+    }
+
+    #[test]
+    fn order_values() {
+        let mut values: Vec<StatValue> = ["∞", "1+*", "*", "-1", "1", "0", "X"]
+            .iter()
+            .map(|s| s.parse().unwrap())
+            .collect();
+        values.sort();
+        let texts: Vec<String> = values.iter().map(|v| v.to_string()).collect();
+        assert_eq!(texts, vec!["-1", "0", "*", "X", "1", "1+*", "∞"]);
+        assert!(StatValue::from(4) <= "4".parse().unwrap());
+        assert_ne!(StatValue::from(0), "*".parse().unwrap());
+    }
+}
@@ -1,9 +1,16 @@
 //! Get card sub types (e.g.: Adventure, Elemental, Trap, Wizard).
+//!
+//! `all()` mixes the subtypes of every card type. `SubtypeCatalog` tells which card types each subtype belongs to
+//! (e.g.: "Goblin" is a creature type, "Island" a land type), from a bundled baseline and the cards crawled.
 #![allow(dead_code)]
+use crate::cards::Card;
+use crate::client::Client;
 use crate::query_builder;
-use reqwest::StatusCode;
+use crate::type_line::{TypeIssue, TypeLine};
+use http::StatusCode;
 use serde::Deserialize;
-use std::collections::HashSet;
+use std::borrow::Borrow;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 
 #[doc(hidden)]
 #[derive(Clone, Debug, Deserialize)]
@@ -26,10 +33,398 @@ pub struct RootAll {
 /// If the call fails, it will return a `Err(StatusCode)`.
 /// To see the possible return values, check the [API docs](https://docs.magicthegathering.io/#documentationerrors).
 pub async fn all() -> Result<HashSet<String>, StatusCode> {
-    let subtypes: Result<RootAll, StatusCode> = query_builder::all("subtypes").await;
+    all_with(&Client::default()).await
+}
+
+/// Function to get all subtypes with the given `Client` (see `client`).
+///
+/// # Example
+/// ```rust
+/// use mtgsdk::client::Client;
+/// use mtgsdk::subtypes;
+/// async {
+///    let client = Client::default();
+///    let subtypes = subtypes::all_with(&client).await;
+///    assert!(!subtypes.unwrap().is_empty());
+/// };
+///```
+///
+/// # Errors
+/// If the call fails, it will return a `Err(StatusCode)`.
+/// To see the possible return values, check the [API docs](https://docs.magicthegathering.io/#documentationerrors).
+pub async fn all_with(client: &Client) -> Result<HashSet<String>, StatusCode> {
+    let subtypes: Result<RootAll, StatusCode> = query_builder::all(client, "subtypes").await;
 
     match subtypes {
         Ok(t) => Ok(t.subtypes),
         Err(e) => Err(e),
     }
 }
+
+/// Card types a subtype may belong to ("spell" types are the subtypes of instants and sorceries).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum SubtypeKind {
+    Creature,
+    Planeswalker,
+    Land,
+    Artifact,
+    Enchantment,
+    Spell,
+}
+
+impl SubtypeKind {
+    /// The kind of subtypes of a card type (e.g.: "Instant" has spell types). Kindred (formerly Tribal) cards
+    /// have creature types.
+    pub fn of_type(card_type: &str) -> Option<SubtypeKind> {
+        let kind = match card_type.to_lowercase().as_str() {
+            "creature" | "tribal" | "kindred" => SubtypeKind::Creature,
+            "planeswalker" => SubtypeKind::Planeswalker,
+            "land" => SubtypeKind::Land,
+            "artifact" => SubtypeKind::Artifact,
+            "enchantment" => SubtypeKind::Enchantment,
+            "instant" | "sorcery" => SubtypeKind::Spell,
+            _ => return None,
+        };
+        Some(kind)
+    }
+
+    // Kinds of a card's (or face's) types: kindred cards have creature types on top of their other types' ones
+    fn of_types<'t>(types: impl IntoIterator<Item = &'t str>) -> BTreeSet<SubtypeKind> {
+        types.into_iter().filter_map(SubtypeKind::of_type).collect()
+    }
+}
+
+// Known subtypes of each kind; creature types are too many to list, so only the most common are here
+const BASELINE: [(SubtypeKind, &[&str]); 6] = [
+    (
+        SubtypeKind::Land,
+        &[
+            "Plains",
+            "Island",
+            "Swamp",
+            "Mountain",
+            "Forest",
+            "Desert",
+            "Gate",
+            "Lair",
+            "Locus",
+            "Mine",
+            "Power-Plant",
+            "Tower",
+            "Urza's",
+            "Cave",
+            "Sphere",
+            "Town",
+        ],
+    ),
+    (
+        SubtypeKind::Artifact,
+        &[
+            "Equipment",
+            "Vehicle",
+            "Fortification",
+            "Contraption",
+            "Clue",
+            "Food",
+            "Treasure",
+            "Gold",
+            "Blood",
+            "Powerstone",
+            "Attraction",
+            "Map",
+            "Incubator",
+            "Bobblehead",
+            "Spacecraft",
+        ],
+    ),
+    (
+        SubtypeKind::Enchantment,
+        &[
+            "Aura",
+            "Saga",
+            "Shrine",
+            "Curse",
+            "Cartouche",
+            "Class",
+            "Rune",
+            "Background",
+            "Role",
+            "Shard",
+            "Case",
+            "Room",
+        ],
+    ),
+    (
+        SubtypeKind::Spell,
+        &["Arcane", "Trap", "Adventure", "Lesson", "Omen"],
+    ),
+    (
+        SubtypeKind::Planeswalker,
+        &[
+            "Ajani", "Chandra", "Elspeth", "Garruk", "Gideon", "Jace", "Karn", "Liliana", "Nissa",
+            "Sorin", "Tezzeret", "Ugin", "Bolas", "Teferi", "Vraska", "Sarkhan", "Nixilis",
+            "Ashiok", "Kaya", "Narset", "Oko", "Tamiyo", "Kiora", "Nahiri", "Dovin", "Domri",
+            "Ral", "Vivien", "Wrenn", "Kasmina", "Tibalt", "Davriel", "Lukka", "Grist",
+        ],
+    ),
+    (
+        SubtypeKind::Creature,
+        &[
+            "Human",
+            "Elf",
+            "Goblin",
+            "Merfolk",
+            "Zombie",
+            "Vampire",
+            "Dragon",
+            "Angel",
+            "Demon",
+            "Beast",
+            "Spirit",
+            "Elemental",
+            "Wizard",
+            "Warrior",
+            "Soldier",
+            "Cleric",
+            "Rogue",
+            "Knight",
+            "Shaman",
+            "Druid",
+            "Giant",
+            "Bird",
+            "Cat",
+            "Dog",
+            "Wolf",
+            "Insect",
+            "Sliver",
+            "Eldrazi",
+            "Golem",
+            "Construct",
+            "Faerie",
+            "Horror",
+            "Dwarf",
+            "Treefolk",
+            "Wall",
+            "Dryad",
+            "Thopter",
+            "Servo",
+            "Myr",
+            "Phyrexian",
+            "Ninja",
+            "Pirate",
+            "Dinosaur",
+        ],
+    ),
+];
+
+// For subtypes only seen on cards of many kinds (e.g.: "Artifact Creature — Golem"), the first kind of these
+const PRIORITY: [SubtypeKind; 6] = [
+    SubtypeKind::Creature,
+    SubtypeKind::Planeswalker,
+    SubtypeKind::Land,
+    SubtypeKind::Artifact,
+    SubtypeKind::Enchantment,
+    SubtypeKind::Spell,
+];
+
+/// Subtypes classified by the card types they belong to.
+///
+/// # Example
+/// ```rust
+/// use mtgsdk::cards::Card;
+/// use mtgsdk::subtypes::{SubtypeCatalog, SubtypeKind};
+///
+/// let card = Card {
+///     types: vec!["Creature".to_string()].into_iter().collect(),
+///     subtypes: Some(vec!["Kithkin".to_string()].into_iter().collect()),
+///     ..Default::default()
+/// };
+/// let catalog = SubtypeCatalog::from_cards(vec![card]);
+/// assert!(catalog.of_kind(SubtypeKind::Creature).contains(&"Kithkin"));
+/// assert!(catalog.is_legal("Island", &["Land"]));
+/// assert!(!catalog.is_legal("Equipment", &["Creature"]));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SubtypeCatalog {
+    kinds: BTreeMap<String, BTreeSet<SubtypeKind>>,
+}
+
+impl SubtypeCatalog {
+    /// The bundled subtypes only.
+    pub fn baseline() -> Self {
+        let mut catalog = SubtypeCatalog::default();
+        for (kind, subtypes) in BASELINE.iter() {
+            for subtype in subtypes.iter() {
+                catalog.insert(subtype, *kind);
+            }
+        }
+        catalog
+    }
+
+    /// The bundled subtypes, and the ones of the cards classified by the types they appear with.
+    ///
+    /// A subtype seen on cards with a single kind of types (e.g.: "Creature — Kithkin") belongs to that kind.
+    /// A subtype only seen with many (e.g.: "Artifact Creature — Golem") belongs to the first of: creature,
+    /// planeswalker, land, artifact, enchantment, spell. The bundled subtypes keep their kinds.
+    pub fn from_cards<I, C>(cards: I) -> Self
+    where
+        I: IntoIterator<Item = C>,
+        C: Borrow<Card>,
+    {
+        let mut single: HashMap<String, BTreeSet<SubtypeKind>> = HashMap::new();
+        let mut many: HashMap<String, BTreeSet<SubtypeKind>> = HashMap::new();
+
+        for card in cards {
+            let card = card.borrow();
+            let kinds = SubtypeKind::of_types(card.types.iter().map(String::as_str));
+            let seen = if kinds.len() == 1 {
+                &mut single
+            } else {
+                &mut many
+            };
+            for subtype in card.subtypes.iter().flatten() {
+                seen.entry(subtype.clone())
+                    .or_default()
+                    .extend(kinds.iter().copied());
+            }
+        }
+
+        let mut catalog = SubtypeCatalog::baseline();
+        for (subtype, kinds) in single {
+            catalog.kinds.entry(subtype).or_insert(kinds);
+        }
+        for (subtype, kinds) in many {
+            if catalog.kinds.contains_key(&subtype) {
+                continue;
+            }
+            if let Some(kind) = PRIORITY.iter().find(|k| kinds.contains(k)) {
+                catalog.insert(&subtype, *kind);
+            }
+        }
+        catalog
+    }
+
+    /// Adds a kind to a subtype.
+    pub fn insert(&mut self, subtype: &str, kind: SubtypeKind) {
+        self.kinds
+            .entry(subtype.to_string())
+            .or_default()
+            .insert(kind);
+    }
+
+    pub fn len(&self) -> usize {
+        self.kinds.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.kinds.is_empty()
+    }
+
+    /// Kinds of a subtype, if known.
+    pub fn kinds(&self, subtype: &str) -> Option<&BTreeSet<SubtypeKind>> {
+        self.kinds.get(subtype)
+    }
+
+    /// Subtypes of a kind, in alphabetical order (e.g.: every creature type).
+    pub fn of_kind(&self, kind: SubtypeKind) -> Vec<&str> {
+        self.kinds
+            .iter()
+            .filter(|(_, kinds)| kinds.contains(&kind))
+            .map(|(subtype, _)| subtype.as_str())
+            .collect()
+    }
+
+    /// Whether a subtype may appear with the card types (e.g.: "Aura" with "Enchantment").
+    /// Unknown subtypes are taken as legal.
+    pub fn is_legal(&self, subtype: &str, card_types: &[&str]) -> bool {
+        let allowed = SubtypeKind::of_types(card_types.iter().copied());
+        self.kinds(subtype)
+            .is_none_or(|kinds| kinds.iter().any(|kind| allowed.contains(kind)))
+    }
+
+    /// Subtypes of a type line that do not belong to the types of their face
+    /// (e.g.: "Equipment" in "Creature — Equipment").
+    pub fn validate(&self, line: &TypeLine) -> Vec<TypeIssue> {
+        let mut issues = Vec::new();
+        for face in line.faces() {
+            let types: Vec<&str> = face.types.iter().map(String::as_str).collect();
+            for subtype in face.subtypes.iter() {
+                if !self.is_legal(subtype, &types) {
+                    issues.push(TypeIssue::IllegalSubtype {
+                        subtype: subtype.clone(),
+                        types: face.types.clone(),
+                    });
+                }
+            }
+        }
+        issues
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cards::Card;
+    use crate::fixtures::strings;
+    use crate::subtypes::{SubtypeCatalog, SubtypeKind};
+    use crate::type_line::{TypeIssue, TypeLine};
+
+    fn card(types: &[&str], subtypes: &[&str]) -> Card {
+        Card {
+            types: strings(types),
+            subtypes: Some(strings(subtypes)),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn classify_from_cards() {
+        let catalog = SubtypeCatalog::from_cards(vec![
+            card(&["Creature"], &["Kithkin", "Soldier"]),
+            card(&["Artifact", "Creature"], &["Gnome"]),
+            card(&["Land", "Creature"], &["Forest", "Dryad"]),
+            card(&["Tribal", "Instant"], &["Goblin", "Faerie"]),
+            card(&["Instant"], &["Arcane"]),
+            card(&["Artifact"], &["Jug"]),
+            card(&["Artifact", "Enchantment"], &["Gizmo"]),
+        ]);
+
+        let kinds = |s: &str| {
+            catalog
+                .kinds(s)
+                .unwrap()
+                .iter()
+                .copied()
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(kinds("Kithkin"), vec![SubtypeKind::Creature]);
+        assert_eq!(kinds("Gnome"), vec![SubtypeKind::Creature]);
+        assert_eq!(kinds("Forest"), vec![SubtypeKind::Land]);
+        assert_eq!(kinds("Dryad"), vec![SubtypeKind::Creature]);
+        assert_eq!(kinds("Arcane"), vec![SubtypeKind::Spell]);
+        assert_eq!(kinds("Jug"), vec![SubtypeKind::Artifact]);
+        assert_eq!(kinds("Gizmo"), vec![SubtypeKind::Artifact]);
+
+        let creatures = catalog.of_kind(SubtypeKind::Creature);
+        assert!(creatures.contains(&"Kithkin") && !creatures.contains(&"Jug"));
+        assert!(!catalog.of_kind(SubtypeKind::Spell).contains(&"Goblin"));
+    }
+
+    #[test]
+    fn validate_type_line() {
+        let catalog = SubtypeCatalog::baseline();
+        let line = TypeLine::parse("Artifact Creature — Golem // Kindred Sorcery — Elf").unwrap();
+        assert!(catalog.validate(&line).is_empty());
+        // Obsidian Battle-Axe
+        let line = TypeLine::parse("Tribal Artifact — Warrior Equipment").unwrap();
+        assert!(catalog.validate(&line).is_empty());
+
+        let line = TypeLine::parse("Creature — Equipment Unknownling").unwrap();
+        assert_eq!(
+            catalog.validate(&line),
+            vec![TypeIssue::IllegalSubtype {
+                subtype: "Equipment".to_string(),
+                types: vec!["Creature".to_string()],
+            }]
+        );
+    }
+}
@@ -0,0 +1,377 @@
+//! Read type lines (e.g.: "Legendary Artifact Creature — Human Wizard") into their components.
+//!
+//! `Card::type_field` holds the whole line, while `Card::supertypes`, `Card::types` and `Card::subtypes` hold its parts.
+//! `TypeLine` reads the line (with a face per side of `//`, for split and double-faced cards), writes it back,
+//! and checks its components against the catalogs of `types::all()`, `subtypes::all()` and `supertypes::all()`.
+//!
+//! # Example
+//! ```rust
+//! use mtgsdk::type_line::TypeLine;
+//!
+//! let line: TypeLine = "Legendary Artifact Creature — Human Wizard".parse().unwrap();
+//! assert_eq!(line.faces()[0].supertypes, vec!["Legendary"]);
+//! assert_eq!(line.faces()[0].types, vec!["Artifact", "Creature"]);
+//! assert_eq!(line.faces()[0].subtypes, vec!["Human", "Wizard"]);
+//! assert_eq!(line.to_string(), "Legendary Artifact Creature — Human Wizard");
+//! ```
+#![allow(dead_code)]
+use crate::cards::Card;
+use crate::client::Client;
+use crate::{subtypes, supertypes, types};
+use http::StatusCode;
+use std::collections::{BTreeSet, HashSet};
+use std::fmt;
+use std::str::FromStr;
+
+// Supertypes as given by `supertypes::all()`, to tell them from types without the catalog
+const SUPERTYPES: [&str; 6] = ["Basic", "Host", "Legendary", "Ongoing", "Snow", "World"];
+
+// Subtypes written in more than one word
+const MULTI_WORD_SUBTYPES: [&str; 1] = ["Time Lord"];
+
+// Separators between types and subtypes: em dash, en dash, and a hyphen in older lines
+const DASHES: [&str; 3] = ["—", "–", " - "];
+
+/// Error reading a `TypeLine`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseTypeLineError {
+    input: String,
+}
+
+impl fmt::Display for ParseTypeLineError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid type line: {:?}", self.input)
+    }
+}
+
+impl std::error::Error for ParseTypeLineError {}
+
+/// Types of one face (e.g.: "Legendary Creature — Dragon").
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Face {
+    pub supertypes: Vec<String>,
+    pub types: Vec<String>,
+    pub subtypes: Vec<String>,
+}
+
+impl fmt::Display for Face {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let left: Vec<&str> = self
+            .supertypes
+            .iter()
+            .chain(&self.types)
+            .map(String::as_str)
+            .collect();
+        f.write_str(&left.join(" "))?;
+        if !self.subtypes.is_empty() {
+            write!(f, " — {}", self.subtypes.join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+/// A type line, with a face per side of `//` (e.g.: "Instant // Sorcery").
+///
+/// Written back with an em dash before the subtypes, whatever dash the line had.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TypeLine {
+    faces: Vec<Face>,
+}
+
+impl TypeLine {
+    /// Reads a type line.
+    ///
+    /// # Errors
+    /// If a face has no type (e.g.: "", "— Human") or a dash without subtypes, it will return a `Err(ParseTypeLineError)`.
+    pub fn parse(line: &str) -> Result<TypeLine, ParseTypeLineError> {
+        let error = || ParseTypeLineError {
+            input: line.to_string(),
+        };
+        let faces = line
+            .split("//")
+            .map(|face| parse_face(face).ok_or_else(error))
+            .collect::<Result<Vec<Face>, ParseTypeLineError>>()?;
+        Ok(TypeLine { faces })
+    }
+
+    pub fn faces(&self) -> &[Face] {
+        &self.faces
+    }
+
+    /// Supertypes of every face, without repetitions.
+    pub fn supertypes(&self) -> BTreeSet<&str> {
+        self.faces
+            .iter()
+            .flat_map(|f| f.supertypes.iter().map(String::as_str))
+            .collect()
+    }
+
+    /// Types of every face, without repetitions.
+    pub fn types(&self) -> BTreeSet<&str> {
+        self.faces
+            .iter()
+            .flat_map(|f| f.types.iter().map(String::as_str))
+            .collect()
+    }
+
+    /// Subtypes of every face, without repetitions.
+    pub fn subtypes(&self) -> BTreeSet<&str> {
+        self.faces
+            .iter()
+            .flat_map(|f| f.subtypes.iter().map(String::as_str))
+            .collect()
+    }
+
+    /// Components missing from the catalogs.
+    pub fn validate(&self, catalogs: &TypeCatalogs) -> Vec<TypeIssue> {
+        let mut issues = Vec::new();
+        for supertype in self.supertypes() {
+            if !catalogs.supertypes.contains(supertype) {
+                issues.push(TypeIssue::UnknownSupertype(supertype.to_string()));
+            }
+        }
+        for t in self.types() {
+            if !catalogs.types.contains(t) {
+                issues.push(TypeIssue::UnknownType(t.to_string()));
+            }
+        }
+        for subtype in self.subtypes() {
+            if !catalogs.subtypes.contains(subtype) {
+                issues.push(TypeIssue::UnknownSubtype(subtype.to_string()));
+            }
+        }
+        issues
+    }
+}
+
+fn parse_face(face: &str) -> Option<Face> {
+    let (left, right) = match DASHES.iter().find_map(|dash| face.split_once(dash)) {
+        Some((left, right)) => (left, Some(right)),
+        None => (face, None),
+    };
+
+    let mut parsed = Face::default();
+    for word in left.split_whitespace() {
+        if SUPERTYPES.contains(&word) {
+            parsed.supertypes.push(word.to_string());
+        } else {
+            parsed.types.push(word.to_string());
+        }
+    }
+    if parsed.types.is_empty() {
+        return None;
+    }
+
+    if let Some(right) = right {
+        let words: Vec<&str> = right.split_whitespace().collect();
+        if words.is_empty() {
+            return None;
+        }
+        let mut i = 0;
+        while i < words.len() {
+            let pair = words
+                .get(i + 1)
+                .map(|next| format!("{} {}", words[i], next));
+            match pair.filter(|pair| MULTI_WORD_SUBTYPES.contains(&pair.as_str())) {
+                Some(pair) => {
+                    parsed.subtypes.push(pair);
+                    i += 2;
+                }
+                None => {
+                    parsed.subtypes.push(words[i].to_string());
+                    i += 1;
+                }
+            }
+        }
+    }
+    Some(parsed)
+}
+
+impl fmt::Display for TypeLine {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, face) in self.faces.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" // ")?;
+            }
+            write!(f, "{}", face)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for TypeLine {
+    type Err = ParseTypeLineError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        TypeLine::parse(s)
+    }
+}
+
+/// The catalogs of `types::all()`, `subtypes::all()` and `supertypes::all()`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TypeCatalogs {
+    pub types: HashSet<String>,
+    pub subtypes: HashSet<String>,
+    pub supertypes: HashSet<String>,
+}
+
+impl TypeCatalogs {
+    /// Function to get the three catalogs, at the same time.
+    ///
+    /// # Errors
+    /// If any call fails, it will return a `Err(StatusCode)`.
+    pub async fn fetch() -> Result<TypeCatalogs, StatusCode> {
+        TypeCatalogs::fetch_with(&Client::default()).await
+    }
+
+    /// Function to get the three catalogs, at the same time, with the given `Client`.
+    ///
+    /// # Errors
+    /// If any call fails, it will return a `Err(StatusCode)`.
+    pub async fn fetch_with(client: &Client) -> Result<TypeCatalogs, StatusCode> {
+        let (types, subtypes, supertypes) = futures::try_join!(
+            types::all_with(client),
+            subtypes::all_with(client),
+            supertypes::all_with(client)
+        )?;
+        Ok(TypeCatalogs {
+            types,
+            subtypes,
+            supertypes,
+        })
+    }
+}
+
+/// A problem with a card's types.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TypeIssue {
+    /// `Card::type_field` cannot be read.
+    Unreadable(String),
+    UnknownSupertype(String),
+    UnknownType(String),
+    UnknownSubtype(String),
+    /// A subtype that does not belong to the types of its face (see `subtypes::SubtypeCatalog::validate()`).
+    IllegalSubtype {
+        subtype: String,
+        types: Vec<String>,
+    },
+    /// `Card::supertypes`, `types` or `subtypes` (named by `field`) disagrees with the type line.
+    Inconsistent {
+        field: &'static str,
+        line: BTreeSet<String>,
+        card: BTreeSet<String>,
+    },
+}
+
+impl Card {
+    /// `type_field` as a `TypeLine`.
+    ///
+    /// # Errors
+    /// If the type line cannot be read, it will return a `Err(ParseTypeLineError)`.
+    pub fn type_line(&self) -> Result<TypeLine, ParseTypeLineError> {
+        TypeLine::parse(&self.type_field)
+    }
+
+    /// Checks the card's type line against the catalogs, and its `supertypes`, `types` and `subtypes` against the line.
+    ///
+    /// For cards with many faces, the API gives the type line of the face only, or of all of them:
+    /// the components are compared with every face of the line.
+    pub fn validate_types(&self, catalogs: &TypeCatalogs) -> Vec<TypeIssue> {
+        let line = match self.type_line() {
+            Ok(line) => line,
+            Err(_) => return vec![TypeIssue::Unreadable(self.type_field.clone())],
+        };
+        let mut issues = line.validate(catalogs);
+
+        let owned = |set: BTreeSet<&str>| -> BTreeSet<String> {
+            set.into_iter().map(String::from).collect()
+        };
+        let fields = [
+            (
+                "supertypes",
+                owned(line.supertypes()),
+                self.supertypes.as_ref(),
+            ),
+            ("types", owned(line.types()), Some(&self.types)),
+            ("subtypes", owned(line.subtypes()), self.subtypes.as_ref()),
+        ];
+        for (field, line, card) in fields {
+            let card: BTreeSet<String> = card.into_iter().flatten().cloned().collect();
+            if card != line {
+                issues.push(TypeIssue::Inconsistent { field, line, card });
+            }
+        }
+        issues
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cards::Card;
+    use crate::type_line::{TypeCatalogs, TypeIssue, TypeLine};
+
+    fn set(values: &[&str]) -> std::collections::HashSet<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn parse_and_format() {
+        let line = TypeLine::parse("Instant // Sorcery").unwrap();
+        assert_eq!(line.faces().len(), 2);
+        assert_eq!(line.to_string(), "Instant // Sorcery");
+
+        let line = TypeLine::parse("Legendary Creature - Time Lord Doctor").unwrap();
+        assert_eq!(line.faces()[0].subtypes, vec!["Time Lord", "Doctor"]);
+        assert_eq!(line.to_string(), "Legendary Creature — Time Lord Doctor");
+
+        let line = TypeLine::parse("Creature — Human Werewolf // Creature — Werewolf").unwrap();
+        assert_eq!(
+            line.subtypes().into_iter().collect::<Vec<_>>(),
+            vec!["Human", "Werewolf"]
+        );
+        assert_eq!(
+            line.to_string(),
+            "Creature — Human Werewolf // Creature — Werewolf"
+        );
+
+        assert!(TypeLine::parse("").is_err());
+        assert!(TypeLine::parse("— Human").is_err());
+        assert!(TypeLine::parse("Creature —").is_err());
+        assert!(TypeLine::parse("Instant //").is_err());
+    }
+
+    #[test]
+    fn validate_card() {
+        let catalogs = TypeCatalogs {
+            types: set(&["Artifact", "Creature"]),
+            subtypes: set(&["Human", "Wizard"]),
+            supertypes: set(&["Legendary"]),
+        };
+        let mut card = Card {
+            type_field: "Legendary Artifact Creature — Human Wizard".to_string(),
+            supertypes: Some(set(&["Legendary"])),
+            types: set(&["Artifact", "Creature"]),
+            subtypes: Some(set(&["Human", "Wizard"])),
+            ..Default::default()
+        };
+        assert!(card.validate_types(&catalogs).is_empty());
+
+        card.type_field = "Legendary Artifact Creature — Human Wizzard".to_string();
+        let issues = card.validate_types(&catalogs);
+        assert_eq!(issues[0], TypeIssue::UnknownSubtype("Wizzard".to_string()));
+        assert!(matches!(
+            issues[1],
+            TypeIssue::Inconsistent {
+                field: "subtypes",
+                ..
+            }
+        ));
+        assert_eq!(issues.len(), 2);
+
+        card.type_field = String::new();
+        assert_eq!(
+            card.validate_types(&catalogs),
+            vec![TypeIssue::Unreadable(String::new())]
+        );
+    }
+}
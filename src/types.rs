@@ -1,7 +1,8 @@
 //! Get card types (e.g.: Artifact, Land, Sorcery).
 #![allow(dead_code)]
+use crate::client::Client;
 use crate::query_builder;
-use reqwest::StatusCode;
+use http::StatusCode;
 use serde::Deserialize;
 use std::collections::HashSet;
 
@@ -26,7 +27,27 @@ pub struct RootAll {
 /// If the call fails, it will return a `Err(StatusCode)`.
 /// To see the possible return values, check the [API docs](https://docs.magicthegathering.io/#documentationerrors).
 pub async fn all() -> Result<HashSet<String>, StatusCode> {
-    let types: Result<RootAll, StatusCode> = query_builder::all("types").await;
+    all_with(&Client::default()).await
+}
+
+/// Function to get all types with the given `Client` (see `client`).
+///
+/// # Example
+/// ```rust
+/// use mtgsdk::client::Client;
+/// use mtgsdk::types;
+/// async {
+///    let client = Client::default();
+///    let types = types::all_with(&client).await;
+///    assert!(!types.unwrap().is_empty());
+/// };
+///```
+///
+/// # Errors
+/// If the call fails, it will return a `Err(StatusCode)`.
+/// To see the possible return values, check the [API docs](https://docs.magicthegathering.io/#documentationerrors).
+pub async fn all_with(client: &Client) -> Result<HashSet<String>, StatusCode> {
+    let types: Result<RootAll, StatusCode> = query_builder::all(client, "types").await;
 
     match types {
         Ok(t) => Ok(t.types),